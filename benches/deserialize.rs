@@ -0,0 +1,124 @@
+//! Node deserialization benchmark against a generated large file
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use figma_cli::models::document::FigmaFile;
+use serde_json::{Value, json};
+use std::hint::black_box;
+
+const PAGES: usize = 10;
+const FRAMES_PER_PAGE: usize = 50;
+
+fn bounds(x: usize, y: usize) -> Value {
+    json!({ "x": x, "y": y, "width": 375.0, "height": 812.0 })
+}
+
+fn solid() -> Value {
+    json!([{ "type": "SOLID", "blendMode": "NORMAL", "color": { "r": 0.1, "g": 0.2, "b": 0.3, "a": 1.0 } }])
+}
+
+fn leaf(node_type: &str, id: String) -> Value {
+    json!({
+        "id": id,
+        "name": node_type,
+        "type": node_type,
+        "absoluteBoundingBox": bounds(0, 0),
+        "fills": solid(),
+        "strokes": solid(),
+        "strokeWeight": 1.0
+    })
+}
+
+fn frame(page: usize, index: usize) -> Value {
+    let id = |n: usize| format!("{page}:{index}:{n}");
+    let mut children = Vec::new();
+    for n in 0..20 {
+        children.push(json!({
+            "id": id(n),
+            "name": "Label",
+            "type": "TEXT",
+            "characters": format!("Text node {n} in frame {index}"),
+            "absoluteBoundingBox": bounds(n, n),
+            "style": { "fontFamily": "Inter", "fontSize": 14.0, "fontWeight": 400, "lineHeightPx": 20.0 }
+        }));
+    }
+    for (n, node_type) in [
+        "RECTANGLE",
+        "VECTOR",
+        "ELLIPSE",
+        "LINE",
+        "STAR",
+        "REGULAR_POLYGON",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        children.push(leaf(node_type, id(100 + n)));
+    }
+    children.push(json!({
+        "id": id(200),
+        "name": "Button",
+        "type": "INSTANCE",
+        "componentId": "9:1",
+        "layoutMode": "HORIZONTAL",
+        "itemSpacing": 8.0,
+        "children": [leaf("RECTANGLE", id(201))]
+    }));
+
+    json!({
+        "id": format!("{page}:{index}"),
+        "name": format!("Screen {index}"),
+        "type": "FRAME",
+        "absoluteBoundingBox": bounds(index * 400, 0),
+        "fills": solid(),
+        "layoutMode": "VERTICAL",
+        "paddingTop": 16.0,
+        "effects": [{ "type": "DROP_SHADOW", "radius": 4.0, "offset": { "x": 0.0, "y": 2.0 } }],
+        "children": [{
+            "id": format!("{page}:{index}:g"),
+            "name": "Content",
+            "type": "GROUP",
+            "children": children
+        }]
+    })
+}
+
+fn large_file() -> String {
+    let pages: Vec<Value> = (0..PAGES)
+        .map(|page| {
+            json!({
+                "id": format!("0:{page}"),
+                "name": format!("Page {page}"),
+                "type": "CANVAS",
+                "children": (0..FRAMES_PER_PAGE).map(|i| frame(page, i)).collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    json!({
+        "name": "Benchmark",
+        "version": "1",
+        "lastModified": "2025-01-01T00:00:00Z",
+        "editorType": "figma",
+        "document": { "id": "0:0", "name": "Document", "type": "DOCUMENT", "children": pages }
+    })
+    .to_string()
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    let json = large_file();
+    let file: FigmaFile = serde_json::from_str(&json).unwrap();
+
+    let mut group = c.benchmark_group("document");
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    group.sample_size(20);
+    group.bench_function("deserialize", |b| {
+        b.iter(|| serde_json::from_str::<FigmaFile>(black_box(&json)).unwrap());
+    });
+    group.bench_function("serialize", |b| {
+        b.iter(|| serde_json::to_string(black_box(&file)).unwrap());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_deserialize);
+criterion_main!(benches);
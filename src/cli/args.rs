@@ -13,6 +13,10 @@ pub struct Cli {
     /// Enable verbose logging
     #[arg(short, long, global = true)]
     pub verbose: bool,
+
+    /// Auth profile to use instead of the active one
+    #[arg(long, global = true, env = "FIGMA_PROFILE")]
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -20,6 +24,12 @@ pub enum Commands {
     /// Extract content from a Figma file
     Extract(ExtractArgs),
 
+    /// Extract many files from a manifest or a project
+    ExtractBatch(ExtractBatchArgs),
+
+    /// Re-extract a file whenever a new version is saved
+    Watch(WatchArgs),
+
     /// Inspect specific nodes from a Figma file
     Inspect(InspectArgs),
 
@@ -29,6 +39,30 @@ pub enum Commands {
     /// Query Figma data using JMESPath
     Query(QueryArgs),
 
+    /// List the version history of a Figma file
+    Versions(VersionsArgs),
+
+    /// List the branches of a Figma file
+    Branches(BranchesArgs),
+
+    /// Compare two versions of a Figma file
+    Diff(DiffArgs),
+
+    /// Read and post file comments
+    Comments(CommentsArgs),
+
+    /// Receive Figma webhooks and extract the files they report
+    ServeWebhooks(ServeWebhooksArgs),
+
+    /// Create, list and delete Figma webhooks
+    Webhooks(WebhooksArgs),
+
+    /// List the projects of a team
+    Projects(ProjectsArgs),
+
+    /// List the files of a project or team
+    Files(FilesArgs),
+
     /// Manage cache
     Cache(CacheArgs),
 
@@ -108,6 +142,102 @@ pub struct ExtractArgs {
     /// Depth of tree traversal (1=pages only, 2=pages+top-level objects, etc.)
     #[arg(long)]
     pub depth: Option<u32>,
+
+    /// Read the file as of a saved version (see `versions`)
+    #[arg(long, value_name = "ID")]
+    pub at_version: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ExtractBatchArgs {
+    /// Manifest (TOML or JSON) listing the files to extract
+    #[arg(value_name = "MANIFEST", required_unless_present = "project")]
+    pub manifest: Option<String>,
+
+    /// Extract every file of a project instead (ID or URL)
+    #[arg(long, conflicts_with = "manifest")]
+    pub project: Option<String>,
+
+    /// Output format for entries that do not set one
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: OutputFormat,
+
+    /// Directory for entries without an output path
+    #[arg(long, default_value = ".")]
+    pub output_dir: String,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Depth of tree traversal for entries that do not set one
+    #[arg(long)]
+    pub depth: Option<u32>,
+
+    /// Maximum files extracted at once (default: performance.concurrent)
+    #[arg(short = 'j', long)]
+    pub concurrency: Option<usize>,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct WatchArgs {
+    /// Figma file URL or key
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Output file path, rewritten on every change
+    #[arg(short, long)]
+    pub output: String,
+
+    /// Output format
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: OutputFormat,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Filter by page names (comma-separated)
+    #[arg(short, long, value_delimiter = ',')]
+    pub pages: Option<Vec<String>>,
+
+    /// Filter by page name pattern (regex)
+    #[arg(long)]
+    pub page_pattern: Option<String>,
+
+    /// Filter by frame name pattern (regex)
+    #[arg(long)]
+    pub frame_pattern: Option<String>,
+
+    /// Include hidden nodes
+    #[arg(long)]
+    pub include_hidden: bool,
+
+    /// Depth of tree traversal
+    #[arg(long)]
+    pub depth: Option<u32>,
+
+    /// Seconds between version checks
+    #[arg(long, default_value = "60")]
+    pub interval: u64,
+
+    /// Shell command to run after each extraction
+    ///
+    /// FIGMA_FILE_KEY, FIGMA_VERSION and FIGMA_OUTPUT are set in its environment.
+    #[arg(long, value_name = "COMMAND")]
+    pub exec: Option<String>,
+
+    /// Stop after this many extractions
+    #[arg(long, value_name = "N")]
+    pub max_runs: Option<usize>,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -124,6 +254,10 @@ pub struct InspectArgs {
     #[arg(long, default_value = "1")]
     pub depth: u32,
 
+    /// Read the file as of a saved version (see `versions`)
+    #[arg(long, value_name = "ID")]
+    pub at_version: Option<String>,
+
     /// Output file path (default: stdout)
     #[arg(short, long)]
     pub output: Option<String>,
@@ -167,6 +301,18 @@ pub struct ImagesArgs {
     #[arg(long, default_value = "2.0")]
     pub scale: f64,
 
+    /// Download the original bitmaps behind image fills instead of rendering frames
+    #[arg(long, conflicts_with = "base64")]
+    pub fills: bool,
+
+    /// Directory for downloaded image fills; the manifest goes here unless --output is given
+    #[arg(long, default_value = "./images", requires = "fills")]
+    pub image_dir: String,
+
+    /// Maximum concurrent image fill downloads
+    #[arg(long, default_value = "50", requires = "fills")]
+    pub max_concurrent: usize,
+
     /// Config file path
     #[arg(short = 'c', long)]
     pub config: Option<String>,
@@ -180,18 +326,66 @@ pub struct AuthArgs {
 
 #[derive(Subcommand, Debug)]
 pub enum AuthCommand {
-    /// Login with a personal access token
-    Login {
-        /// Figma personal access token
-        #[arg(value_name = "TOKEN")]
-        token: Option<String>,
-    },
+    /// Login with a personal access token, or through the browser with --oauth
+    Login(AuthLoginArgs),
 
     /// Test authentication
     Test,
 
     /// Logout (remove stored token)
     Logout,
+
+    /// List stored profiles
+    List {
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Make a profile the one used by default
+    Use {
+        /// Profile name
+        profile: String,
+    },
+}
+
+#[derive(Parser, Debug)]
+pub struct AuthLoginArgs {
+    /// Figma personal access token
+    #[arg(value_name = "TOKEN", conflicts_with = "oauth")]
+    pub token: Option<String>,
+
+    /// Authorize an OAuth app in the browser instead
+    #[arg(long)]
+    pub oauth: bool,
+
+    /// OAuth app client ID
+    #[arg(long, env = "FIGMA_CLIENT_ID")]
+    pub client_id: Option<String>,
+
+    /// OAuth app client secret
+    #[arg(long, env = "FIGMA_CLIENT_SECRET", hide_env_values = true)]
+    pub client_secret: Option<String>,
+
+    /// Local port for the redirect; register http://localhost:<PORT>/callback with the app
+    #[arg(long, default_value = "8976")]
+    pub port: u16,
+
+    /// OAuth scopes (comma-separated)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "current_user:read,file_content:read,file_metadata:read,file_versions:read,file_comments:read,file_comments:write,projects:read,webhooks:read,webhooks:write"
+    )]
+    pub scopes: Vec<String>,
+
+    /// Print the authorization URL instead of opening a browser
+    #[arg(long)]
+    pub no_browser: bool,
+
+    /// Default team for this profile's `projects` and `files`
+    #[arg(long)]
+    pub team: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -212,6 +406,434 @@ pub struct QueryArgs {
     #[arg(long)]
     pub depth: Option<u32>,
 
+    /// Read the file as of a saved version (see `versions`)
+    #[arg(long, value_name = "ID")]
+    pub at_version: Option<String>,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct VersionsArgs {
+    /// Figma file URL or key
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Maximum number of versions to list, newest first (default: all)
+    #[arg(short = 'n', long)]
+    pub limit: Option<usize>,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct BranchesArgs {
+    /// Figma file URL or key; a branch URL lists the branches of its main file
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Output format
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: ListFormat,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct DiffArgs {
+    /// Figma file URL or key (optional when both sides are snapshots)
+    #[arg(value_name = "FILE")]
+    pub file: Option<String>,
+
+    /// Version ID of the older side
+    #[arg(long, value_name = "ID", conflicts_with = "from_snapshot")]
+    pub from: Option<String>,
+
+    /// Version ID of the newer side (default: latest)
+    #[arg(long, value_name = "ID", conflicts_with = "to_snapshot")]
+    pub to: Option<String>,
+
+    /// Read the older side from a saved file JSON or cache entry
+    #[arg(long, value_name = "PATH")]
+    pub from_snapshot: Option<String>,
+
+    /// Read the newer side from a saved file JSON or cache entry
+    #[arg(long, value_name = "PATH")]
+    pub to_snapshot: Option<String>,
+
+    /// Output format
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: DiffFormat,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CommentsArgs {
+    #[command(subcommand)]
+    pub command: CommentsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CommentsCommand {
+    /// List comment threads with the nodes they are pinned to
+    List(CommentsListArgs),
+
+    /// Post a comment, optionally pinned to a node
+    Post(CommentsPostArgs),
+
+    /// Reply to an existing comment
+    Reply(CommentsReplyArgs),
+
+    /// Delete a comment you authored
+    Delete(CommentsDeleteArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct CommentsListArgs {
+    /// Figma file URL or key
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Only open or only resolved threads
+    #[arg(long, default_value = "all")]
+    pub status: CommentStatus,
+
+    /// Only threads started by this user handle
+    #[arg(long)]
+    pub author: Option<String>,
+
+    /// Only threads started on or after this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Only threads started on or before this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Output format
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: CommentFormat,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CommentsPostArgs {
+    /// Figma file URL or key (a node-id in the URL pins the comment)
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Node to pin the comment to (e.g. 1:2 or 1-2)
+    #[arg(long)]
+    pub node: Option<String>,
+
+    /// Comment text
+    #[arg(short, long)]
+    pub message: String,
+
+    /// Print the request instead of sending it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CommentsReplyArgs {
+    /// Figma file URL or key
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Id of the comment to reply to
+    #[arg(value_name = "COMMENT_ID")]
+    pub comment_id: String,
+
+    /// Reply text
+    #[arg(short, long)]
+    pub message: String,
+
+    /// Print the request instead of sending it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CommentsDeleteArgs {
+    /// Figma file URL or key
+    #[arg(value_name = "FILE")]
+    pub file: String,
+
+    /// Id of the comment to delete
+    #[arg(value_name = "COMMENT_ID")]
+    pub comment_id: String,
+
+    /// Print the request instead of sending it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ServeWebhooksArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to listen on
+    #[arg(long, default_value = "8080")]
+    pub port: u16,
+
+    /// Passcode the webhooks were created with
+    #[arg(long, env = "FIGMA_WEBHOOK_PASSCODE", hide_env_values = true)]
+    pub passcode: String,
+
+    /// Events that trigger an extraction (comma-separated)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "file-update,file-version-update,library-publish"
+    )]
+    pub events: Vec<WebhookEventType>,
+
+    /// Manifest (see `extract-batch`) selecting which files to extract and how;
+    /// without one, every reported file is extracted with the defaults below
+    #[arg(long)]
+    pub manifest: Option<String>,
+
+    /// Output format for entries that do not set one
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: OutputFormat,
+
+    /// Directory for entries without an output path
+    #[arg(long, default_value = ".")]
+    pub output_dir: String,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Depth of tree traversal for entries that do not set one
+    #[arg(long)]
+    pub depth: Option<u32>,
+
+    /// Seconds to wait for more events on a file before extracting it
+    #[arg(long, default_value = "5")]
+    pub debounce: u64,
+
+    /// Shell command to run after each extraction
+    ///
+    /// FIGMA_FILE_KEY, FIGMA_EVENT and FIGMA_OUTPUT are set in its environment.
+    #[arg(long, value_name = "COMMAND")]
+    pub exec: Option<String>,
+
+    /// Append-only JSON Lines log of received events and extractions
+    #[arg(long, default_value = "figma-webhooks.jsonl")]
+    pub event_log: String,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct WebhooksArgs {
+    #[command(subcommand)]
+    pub command: WebhooksCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WebhooksCommand {
+    /// Register a webhook
+    Create(WebhooksCreateArgs),
+
+    /// List webhooks of a team, project or file
+    List(WebhooksListArgs),
+
+    /// Delete a webhook
+    Delete(WebhooksDeleteArgs),
+}
+
+/// What a webhook is attached to; defaults to the configured team
+#[derive(clap::Args, Debug)]
+#[group(multiple = false)]
+pub struct WebhookScope {
+    /// Team ID or URL
+    #[arg(long)]
+    pub team: Option<String>,
+
+    /// Project ID or URL
+    #[arg(long)]
+    pub project: Option<String>,
+
+    /// File URL or key
+    #[arg(long)]
+    pub file: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct WebhooksCreateArgs {
+    /// Event to subscribe to
+    #[arg(long)]
+    pub event: WebhookEventType,
+
+    #[command(flatten)]
+    pub scope: WebhookScope,
+
+    /// URL Figma will POST events to
+    #[arg(long)]
+    pub endpoint: String,
+
+    /// Passcode sent with every event
+    #[arg(long, env = "FIGMA_WEBHOOK_PASSCODE", hide_env_values = true)]
+    pub passcode: String,
+
+    /// Free-form description
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// Create the webhook without activating it
+    #[arg(long)]
+    pub paused: bool,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct WebhooksListArgs {
+    #[command(flatten)]
+    pub scope: WebhookScope,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct WebhooksDeleteArgs {
+    /// Webhook ID
+    #[arg(value_name = "ID")]
+    pub id: String,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ProjectsArgs {
+    /// Team ID or URL (default: `team` from config)
+    #[arg(long)]
+    pub team: Option<String>,
+
+    /// Output format
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: ListFormat,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Pretty print JSON output
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// Config file path
+    #[arg(short = 'c', long)]
+    pub config: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct FilesArgs {
+    /// Project ID or URL; omit to list every project of the team
+    #[arg(value_name = "PROJECT")]
+    pub project: Option<String>,
+
+    /// Team ID or URL (default: `team` from config)
+    #[arg(long, conflicts_with = "project")]
+    pub team: Option<String>,
+
+    /// Output format
+    #[arg(short = 'f', long, default_value = "json")]
+    pub format: ListFormat,
+
     /// Output file path (default: stdout)
     #[arg(short, long)]
     pub output: Option<String>,
@@ -298,7 +920,8 @@ pub enum ConfigCommand {
     },
 }
 
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     Json,
     Text,
@@ -306,6 +929,55 @@ pub enum OutputFormat {
     Summary,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum WebhookEventType {
+    FileUpdate,
+    FileVersionUpdate,
+    FileComment,
+    LibraryPublish,
+}
+
+impl WebhookEventType {
+    /// Figma's name for the event
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FileUpdate => "FILE_UPDATE",
+            Self::FileVersionUpdate => "FILE_VERSION_UPDATE",
+            Self::FileComment => "FILE_COMMENT",
+            Self::LibraryPublish => "LIBRARY_PUBLISH",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ListFormat {
+    Json,
+    /// Aligned columns for reading in a terminal
+    Table,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum DiffFormat {
+    Json,
+    Markdown,
+    /// Unified-diff style text
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum CommentStatus {
+    All,
+    Open,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum CommentFormat {
+    Json,
+    Markdown,
+    Csv,
+}
+
 impl ExtractArgs {
     pub fn parse_file_key(&self) -> Result<String, String> {
         crate::utils::parse_file_key_from_url(&self.file).map_err(|e| e.to_string())
@@ -0,0 +1,197 @@
+//! Manifest and per-file extraction for `extract-batch`
+
+use crate::cli::args::OutputFormat;
+use crate::cli::commands::build_filter;
+use crate::cli::output::format_output;
+use crate::models::config::ExtractionOptions;
+use crate::service::Orchestrator;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Files to extract, read from TOML or JSON
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchManifest {
+    pub files: Vec<BatchEntry>,
+}
+
+/// One file to extract; unset fields fall back to the command-line defaults
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchEntry {
+    /// Figma file URL or key
+    pub file: String,
+    pub pages: Option<Vec<String>>,
+    pub page_pattern: Option<String>,
+    pub frame_pattern: Option<String>,
+    #[serde(default)]
+    pub include_hidden: bool,
+    pub format: Option<OutputFormat>,
+    pub output: Option<String>,
+    pub depth: Option<u32>,
+    pub pretty: Option<bool>,
+}
+
+/// Settings for entries that leave them unset
+#[derive(Debug, Clone, Copy)]
+pub struct BatchDefaults {
+    pub format: OutputFormat,
+    pub pretty: bool,
+    pub depth: Option<u32>,
+}
+
+impl BatchManifest {
+    /// Load a manifest; `.json` files are parsed as JSON, anything else as TOML
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+
+        let manifest: Self = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content).context("Invalid JSON manifest")?
+        } else {
+            toml::from_str(&content).context("Invalid TOML manifest")?
+        };
+
+        if manifest.files.is_empty() {
+            anyhow::bail!("Manifest lists no files");
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl BatchEntry {
+    pub fn new(file: String) -> Self {
+        Self {
+            file,
+            ..Self::default()
+        }
+    }
+
+    pub fn file_key(&self) -> Result<String> {
+        crate::utils::parse_file_key_from_url(&self.file)
+            .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))
+    }
+
+    /// Explicit output path, or `<dir>/<file key>.<ext>`
+    pub fn output_path(&self, file_key: &str, format: OutputFormat, dir: &Path) -> PathBuf {
+        self.output.as_ref().map_or_else(
+            || dir.join(format!("{file_key}.{}", format.extension())),
+            PathBuf::from,
+        )
+    }
+}
+
+impl OutputFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Text => "txt",
+            Self::Markdown | Self::Summary => "md",
+        }
+    }
+}
+
+/// Extract one entry and write it out, returning the output path
+pub async fn extract_entry(
+    orchestrator: &Orchestrator,
+    entry: &BatchEntry,
+    defaults: BatchDefaults,
+    output_dir: &Path,
+) -> Result<PathBuf> {
+    let file_key = entry.file_key()?;
+
+    let filter = build_filter(
+        entry.pages.clone(),
+        None,
+        entry.page_pattern.as_deref(),
+        entry.frame_pattern.as_deref(),
+        entry.include_hidden,
+    )?;
+    let options = ExtractionOptions {
+        filter,
+        ..ExtractionOptions::default()
+    };
+
+    let result = orchestrator
+        .extract(&file_key, &options, entry.depth.or(defaults.depth), None)
+        .await?;
+
+    let format = entry.format.unwrap_or(defaults.format);
+    let path = entry.output_path(&file_key, format, output_dir);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    format_output(
+        &result,
+        format,
+        Some(&path.display().to_string()),
+        entry.pretty.unwrap_or(defaults.pretty),
+    )?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_toml_and_json() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        let toml_path = dir.path().join("batch.toml");
+        std::fs::write(
+            &toml_path,
+            r#"
+[[files]]
+file = "ABCDEFGHIJKLMNOPQRSTUV"
+pages = ["Mobile"]
+format = "markdown"
+output = "out/checkout.md"
+
+[[files]]
+file = "https://www.figma.com/design/ZYXWVUTSRQPONMLKJIHGFE/Other"
+"#,
+        )
+        .unwrap();
+        let manifest = BatchManifest::load(&toml_path).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert!(matches!(
+            manifest.files[0].format,
+            Some(OutputFormat::Markdown)
+        ));
+
+        let json_path = dir.path().join("batch.json");
+        std::fs::write(
+            &json_path,
+            r#"{ "files": [{ "file": "ABCDEFGHIJKLMNOPQRSTUV", "frame_pattern": "^Cart" }] }"#,
+        )
+        .unwrap();
+        let manifest = BatchManifest::load(&json_path).unwrap();
+        assert_eq!(manifest.files[0].frame_pattern.as_deref(), Some("^Cart"));
+
+        std::fs::write(&json_path, r#"{ "files": [{ "file": "x", "typo": 1 }] }"#).unwrap();
+        assert!(BatchManifest::load(&json_path).is_err());
+    }
+
+    #[test]
+    fn test_output_path() {
+        let entry = BatchEntry::new("KEY".to_string());
+        assert_eq!(
+            entry.output_path("KEY", OutputFormat::Markdown, Path::new("out")),
+            PathBuf::from("out/KEY.md")
+        );
+
+        let entry = BatchEntry {
+            output: Some("custom.json".to_string()),
+            ..BatchEntry::new("KEY".to_string())
+        };
+        assert_eq!(
+            entry.output_path("KEY", OutputFormat::Json, Path::new("out")),
+            PathBuf::from("custom.json")
+        );
+    }
+}
@@ -2,15 +2,32 @@
 
 use super::context::ClientContext;
 use crate::cli::args::{
-    AuthCommand, CacheCommand, ConfigCommand, ExtractArgs, ImagesArgs, InspectArgs, OutputFormat,
-    QueryArgs,
+    AuthCommand, AuthLoginArgs, BranchesArgs, CacheCommand, CommentStatus, CommentsCommand,
+    CommentsDeleteArgs, CommentsListArgs, CommentsPostArgs, CommentsReplyArgs, ConfigCommand,
+    DiffArgs, ExtractArgs, ExtractBatchArgs, FilesArgs, ImagesArgs, InspectArgs, OutputFormat,
+    ProjectsArgs, QueryArgs, ServeWebhooksArgs, VersionsArgs, WatchArgs, WebhookScope,
+    WebhooksCommand, WebhooksCreateArgs, WebhooksDeleteArgs, WebhooksListArgs,
 };
-use crate::cli::output::format_output;
-use crate::client::{FigmaClient, TokenManager};
-use crate::core::{Cache, Config, QueryEngine};
-use crate::models::config::FilterCriteria;
-use crate::service::Orchestrator;
+use crate::cli::batch::{BatchDefaults, BatchEntry, BatchManifest, extract_entry};
+use crate::cli::oauth::{open_browser, wait_for_code};
+use crate::cli::output::{
+    format_branches_output, format_comments_output, format_diff_output, format_files_output,
+    format_output, format_projects_output,
+};
+use crate::cli::webhook_server::{Pipeline, Receiver};
+use crate::client::auth::{Pkce, authorization_url, exchange_code, random_token};
+use crate::client::{Credential, FigmaClient, NewComment, NewWebhook, TokenManager, comments_url};
+use crate::core::errors::Error;
+use crate::core::{Cache, Config, QueryEngine, SecureString};
+use crate::models::comment::CommentFilter;
+use crate::models::config::{ExtractionOptions, FilterCriteria};
+use crate::models::document::FigmaFile;
+use crate::models::project::{
+    BranchListing, FileListing, ListedBranch, ListedFile, ListedProject, ProjectListing,
+};
+use crate::service::{Orchestrator, build_report, diff_files};
 use anyhow::{Context, Result};
+use futures::{StreamExt, stream};
 use std::io::{self, Write};
 
 /// Handle extract command
@@ -23,35 +40,29 @@ pub async fn handle_extract(args: ExtractArgs) -> Result<()> {
 
     let ctx = ClientContext::new(None)?;
 
-    // Build filter criteria
-    let mut filter = FilterCriteria::new();
-
-    if let Some(pages) = args.pages {
-        filter = filter.with_pages(pages);
-    }
-
-    if let Some(page_ids) = args.page_ids {
-        filter = filter.with_page_ids(page_ids);
-    }
-
-    if let Some(pattern) = args.page_pattern {
-        let regex = regex::Regex::new(&pattern).context("Invalid page pattern regex")?;
-        filter = filter.with_page_pattern(regex);
-    }
-
-    if let Some(pattern) = args.frame_pattern {
-        let regex = regex::Regex::new(&pattern).context("Invalid frame pattern regex")?;
-        filter = filter.with_frame_pattern(regex);
-    }
-
-    if args.include_hidden {
-        filter = filter.with_include_hidden(true);
-    }
+    let filter = build_filter(
+        args.pages,
+        args.page_ids,
+        args.page_pattern.as_deref(),
+        args.frame_pattern.as_deref(),
+        args.include_hidden,
+    )?;
+
+    let options = ExtractionOptions {
+        filter,
+        include_metadata: args.with_metadata,
+        include_images: args.with_images,
+        image_dir: Some(args.image_dir),
+        image_format: args.image_format.parse()?,
+        image_scale: args.image_scale,
+        max_concurrent_images: args.max_concurrent,
+        timeout_ms: args.timeout,
+    };
 
     // Create orchestrator and extract
     let orchestrator = Orchestrator::new(ctx.client);
     let result = orchestrator
-        .extract(&file_key, filter, args.depth)
+        .extract(&file_key, &options, args.depth, args.at_version.as_deref())
         .await
         .context("Failed to extract content from Figma file")?;
 
@@ -67,6 +78,12 @@ pub async fn handle_extract(args: ExtractArgs) -> Result<()> {
         println!("  Pages:      {}", result.stats.total_pages);
         println!("  Frames:     {}", result.stats.total_frames);
         println!("  Text nodes: {}", result.stats.total_text_nodes);
+        if let Some(elements) = &result.elements {
+            println!("  Elements:   {}", elements.len());
+        }
+        if let Some(total_images) = result.stats.total_images {
+            println!("  Images:     {total_images}");
+        }
         println!("  Characters: {}", result.stats.total_characters);
         println!("  Time:       {}ms", result.stats.extraction_time_ms);
         println!("  Memory:     {:.2}MB", result.stats.memory_size_mb);
@@ -79,11 +96,406 @@ pub async fn handle_extract(args: ExtractArgs) -> Result<()> {
     Ok(())
 }
 
+/// Filter criteria shared by `extract`, `extract-batch` and `watch`
+pub(super) fn build_filter(
+    pages: Option<Vec<String>>,
+    page_ids: Option<Vec<String>>,
+    page_pattern: Option<&str>,
+    frame_pattern: Option<&str>,
+    include_hidden: bool,
+) -> Result<FilterCriteria> {
+    let mut filter = FilterCriteria::new();
+
+    if let Some(pages) = pages {
+        filter = filter.with_pages(pages);
+    }
+
+    if let Some(page_ids) = page_ids {
+        filter = filter.with_page_ids(page_ids);
+    }
+
+    if let Some(pattern) = page_pattern {
+        let regex = regex::Regex::new(pattern).context("Invalid page pattern regex")?;
+        filter = filter.with_page_pattern(regex);
+    }
+
+    if let Some(pattern) = frame_pattern {
+        let regex = regex::Regex::new(pattern).context("Invalid frame pattern regex")?;
+        filter = filter.with_frame_pattern(regex);
+    }
+
+    if include_hidden {
+        filter = filter.with_include_hidden(true);
+    }
+
+    Ok(filter)
+}
+
+/// Handle extract-batch command
+pub async fn handle_extract_batch(args: ExtractBatchArgs) -> Result<()> {
+    let ctx = ClientContext::new(args.config.as_deref())?;
+
+    let entries = if let Some(project) = &args.project {
+        let project_id = crate::utils::parse_project_id(project)
+            .map_err(|e| anyhow::anyhow!("Failed to parse project ID: {e}"))?;
+        ctx.client
+            .get_project_files(&project_id)
+            .await
+            .context("Failed to fetch project files")?
+            .files
+            .into_iter()
+            .map(|f| BatchEntry::new(f.key))
+            .collect()
+    } else {
+        let manifest = args.manifest.as_deref().context("No manifest given")?;
+        BatchManifest::load(std::path::Path::new(manifest))?.files
+    };
+
+    let concurrency = args
+        .concurrency
+        .unwrap_or(ctx.config.performance.concurrent)
+        .max(1);
+    let total = entries.len();
+
+    tracing::info!(
+        "Extracting {} files, {} at a time",
+        total,
+        concurrency.min(total)
+    );
+
+    // One client, and so one cache, for every file
+    let orchestrator = Orchestrator::new(ctx.client);
+    let output_dir = std::path::Path::new(&args.output_dir);
+    let defaults = BatchDefaults {
+        format: args.format,
+        pretty: args.pretty,
+        depth: args.depth,
+    };
+
+    let mut results = stream::iter(&entries)
+        .map(|entry| {
+            let orchestrator = &orchestrator;
+            async move {
+                let start = std::time::Instant::now();
+                let outcome = extract_entry(orchestrator, entry, defaults, output_dir).await;
+                (entry, outcome, start.elapsed())
+            }
+        })
+        .buffered(concurrency);
+
+    let mut failed = 0;
+    while let Some((entry, outcome, elapsed)) = results.next().await {
+        match outcome {
+            Ok(path) => println!(
+                "✓ {} → {} ({}ms)",
+                entry.file,
+                path.display(),
+                elapsed.as_millis()
+            ),
+            Err(e) => {
+                failed += 1;
+                println!("✗ {}: {e:#}", entry.file);
+            }
+        }
+    }
+
+    println!();
+    println!("Extracted {} of {total} files", total - failed);
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {total} files failed");
+    }
+
+    Ok(())
+}
+
+/// Longest wait between checks while rate limited
+const MAX_WATCH_BACKOFF_SECS: u64 = 900;
+
+/// Handle watch command
+pub async fn handle_watch(args: WatchArgs) -> Result<()> {
+    let file_key = crate::utils::parse_file_key_from_url(&args.file)
+        .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+
+    if args.interval == 0 {
+        anyhow::bail!("--interval must be at least 1 second");
+    }
+
+    let ctx = ClientContext::new(args.config.as_deref())?;
+    let filter = build_filter(
+        args.pages.clone(),
+        None,
+        args.page_pattern.as_deref(),
+        args.frame_pattern.as_deref(),
+        args.include_hidden,
+    )?;
+    let options = ExtractionOptions {
+        filter,
+        ..ExtractionOptions::default()
+    };
+
+    let cache = ctx.cache;
+    let orchestrator = Orchestrator::new(ctx.client);
+    let interval = std::time::Duration::from_secs(args.interval);
+
+    // An existing output built from the cached version is already current
+    let mut last_version = std::path::Path::new(&args.output)
+        .exists()
+        .then(|| cache.cached_version(&file_key))
+        .flatten();
+    let mut last_modified = None;
+    let mut delay = interval;
+    let mut runs = 0;
+
+    println!(
+        "Watching {file_key} every {}s (Ctrl-C to stop)",
+        args.interval
+    );
+
+    loop {
+        match orchestrator.client().get_file_meta(&file_key).await {
+            Ok(meta) => {
+                delay = interval;
+
+                let new_version = last_version.as_deref() != Some(meta.version.as_str());
+                // Ordinary edits move lastModified without a new version
+                let edited = last_modified.is_some_and(|t| t != meta.last_modified);
+                last_modified = Some(meta.last_modified);
+
+                if new_version || edited {
+                    // After an edit, copies cached under the same version are stale too
+                    let keep = (!edited).then_some(meta.version.as_str());
+                    if let Err(e) = cache.evict_latest(&file_key, keep) {
+                        tracing::warn!("Failed to evict stale cache entries: {}", e);
+                    }
+
+                    match watch_extract(&orchestrator, &file_key, &options, &args).await {
+                        Ok(()) => {
+                            println!(
+                                "✓ Version {} ({}) → {}",
+                                meta.version,
+                                meta.last_modified.format("%Y-%m-%d %H:%M:%S UTC"),
+                                args.output
+                            );
+                            if let Some(command) = &args.exec {
+                                run_hook(
+                                    command,
+                                    &[
+                                        ("FIGMA_FILE_KEY", &file_key),
+                                        ("FIGMA_VERSION", &meta.version),
+                                        ("FIGMA_OUTPUT", &args.output),
+                                    ],
+                                )
+                                .await;
+                            }
+                            last_version = Some(meta.version);
+                        }
+                        // Leave the version unseen so the next check retries it
+                        Err(e) => eprintln!("✗ Version {}: {e:#}", meta.version),
+                    }
+
+                    runs += 1;
+                    if args.max_runs.is_some_and(|max| runs >= max) {
+                        return Ok(());
+                    }
+                }
+            }
+            Err(Error::RateLimit) => {
+                delay = (delay * 2).min(std::time::Duration::from_secs(
+                    MAX_WATCH_BACKOFF_SECS.max(args.interval),
+                ));
+                eprintln!("Rate limited, next check in {}s", delay.as_secs());
+            }
+            Err(e @ Error::Network(_)) => {
+                eprintln!("Check failed: {e}; retrying in {}s", delay.as_secs());
+            }
+            Err(e) => return Err(e).context("Failed to check file version"),
+        }
+
+        tokio::select! {
+            () = tokio::time::sleep(delay) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("Stopped watching {file_key}");
+                return Ok(());
+            }
+        }
+    }
+}
+
+async fn watch_extract(
+    orchestrator: &Orchestrator,
+    file_key: &str,
+    options: &ExtractionOptions,
+    args: &WatchArgs,
+) -> Result<()> {
+    let result = orchestrator
+        .extract(file_key, options, args.depth, None)
+        .await
+        .context("Failed to extract content from Figma file")?;
+
+    format_output(&result, args.format, Some(&args.output), args.pretty)
+}
+
+/// Run an `--exec` hook in the shell; its failure is reported but not returned
+pub(super) async fn run_hook(command: &str, env: &[(&str, &str)]) {
+    let mut shell = if cfg!(windows) {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+
+    let status = shell.arg(command).envs(env.iter().copied()).status().await;
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("✗ Command exited with {status}"),
+        Err(e) => eprintln!("✗ Failed to run command: {e}"),
+    }
+}
+
+/// Handle serve-webhooks command
+pub async fn handle_serve_webhooks(args: ServeWebhooksArgs) -> Result<()> {
+    if args.passcode.is_empty() {
+        anyhow::bail!("A passcode is required (--passcode or FIGMA_WEBHOOK_PASSCODE)");
+    }
+
+    let ctx = ClientContext::new(args.config.as_deref())?;
+    let entries = args
+        .manifest
+        .as_deref()
+        .map(|manifest| BatchManifest::load(std::path::Path::new(manifest)).map(|m| m.files))
+        .transpose()?;
+
+    let pipeline = Pipeline {
+        orchestrator: Orchestrator::new(ctx.client),
+        cache: ctx.cache,
+        entries,
+        defaults: BatchDefaults {
+            format: args.format,
+            pretty: args.pretty,
+            depth: args.depth,
+        },
+        output_dir: std::path::PathBuf::from(&args.output_dir),
+        exec: args.exec,
+    };
+    let events: Vec<&str> = args.events.iter().map(|e| e.as_str()).collect();
+    let events = events.join(", ");
+    let receiver = Receiver::new(
+        args.passcode,
+        args.events,
+        std::time::Duration::from_secs(args.debounce),
+        pipeline,
+        std::path::Path::new(&args.event_log),
+    )?;
+
+    let listener = tokio::net::TcpListener::bind((args.host.as_str(), args.port))
+        .await
+        .with_context(|| format!("Failed to listen on {}:{}", args.host, args.port))?;
+
+    println!(
+        "Listening on http://{} for {events} (Ctrl-C to stop)",
+        listener.local_addr()?
+    );
+
+    std::sync::Arc::new(receiver).serve(listener).await
+}
+
+/// Handle webhooks command
+pub async fn handle_webhooks(command: WebhooksCommand) -> Result<()> {
+    match command {
+        WebhooksCommand::Create(args) => handle_webhooks_create(args).await,
+        WebhooksCommand::List(args) => handle_webhooks_list(args).await,
+        WebhooksCommand::Delete(args) => handle_webhooks_delete(args).await,
+    }
+}
+
+async fn handle_webhooks_create(args: WebhooksCreateArgs) -> Result<()> {
+    let ctx = ClientContext::new(args.config.as_deref())?;
+    let (context, context_id) = resolve_webhook_scope(&args.scope, &ctx.config)?;
+
+    let webhook = ctx
+        .client
+        .create_webhook(&NewWebhook {
+            event_type: args.event.as_str().to_string(),
+            context,
+            context_id,
+            endpoint: args.endpoint,
+            passcode: args.passcode,
+            status: args.paused.then(|| "PAUSED".to_string()),
+            description: args.description,
+        })
+        .await
+        .context("Failed to create webhook")?;
+
+    print_json(&webhook, args.pretty)
+}
+
+async fn handle_webhooks_list(args: WebhooksListArgs) -> Result<()> {
+    let ctx = ClientContext::new(args.config.as_deref())?;
+    let (context, context_id) = resolve_webhook_scope(&args.scope, &ctx.config)?;
+
+    let webhooks = ctx
+        .client
+        .list_webhooks(&context, &context_id)
+        .await
+        .context("Failed to fetch webhooks")?;
+
+    print_json(
+        &serde_json::json!({
+            "context": context,
+            "contextId": context_id,
+            "webhooks": webhooks,
+            "total": webhooks.len(),
+        }),
+        args.pretty,
+    )
+}
+
+async fn handle_webhooks_delete(args: WebhooksDeleteArgs) -> Result<()> {
+    let ctx = ClientContext::new(args.config.as_deref())?;
+
+    ctx.client
+        .delete_webhook(&args.id)
+        .await
+        .context("Failed to delete webhook")?;
+
+    print_json(
+        &serde_json::json!({ "id": args.id, "deleted": true }),
+        false,
+    )
+}
+
+/// Webhook context and its ID; the configured team when no scope is given
+fn resolve_webhook_scope(scope: &WebhookScope, config: &Config) -> Result<(String, String)> {
+    if let Some(file) = &scope.file {
+        let file_key = crate::utils::parse_file_key_from_url(file)
+            .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+        return Ok(("file".to_string(), file_key));
+    }
+
+    if let Some(project) = &scope.project {
+        let project_id = crate::utils::parse_project_id(project)
+            .map_err(|e| anyhow::anyhow!("Failed to parse project ID: {e}"))?;
+        return Ok(("project".to_string(), project_id));
+    }
+
+    Ok((
+        "team".to_string(),
+        resolve_team(scope.team.as_deref(), config)?,
+    ))
+}
+
 /// Handle auth command
 pub async fn handle_auth(command: AuthCommand) -> Result<()> {
     match command {
-        AuthCommand::Login { token } => {
-            let token = if let Some(t) = token {
+        AuthCommand::Login(args) if args.oauth => oauth_login(args).await?,
+
+        AuthCommand::Login(args) => {
+            let token = SecureString::new(if let Some(t) = args.token {
                 t
             } else {
                 // Prompt for token
@@ -93,39 +505,43 @@ pub async fn handle_auth(command: AuthCommand) -> Result<()> {
                 let mut input = String::new();
                 io::stdin().read_line(&mut input)?;
                 input.trim().to_string()
-            };
+            });
 
             // Validate token format
-            if !token.starts_with("figd_") {
+            if !token.as_str().starts_with("figd_") {
                 anyhow::bail!("Invalid token format. Token should start with 'figd_'");
             }
 
             // Create client and test auth
-
-            let client = FigmaClient::new(token.clone())?;
+            let config = Config::load()?;
+            let client = FigmaClient::new(Credential::Personal(token.clone()))?
+                .with_base_url(&config.http.base_url);
             let user_info = client
                 .validate_auth()
                 .await
                 .context("Authentication failed")?;
 
             // Store token
-            TokenManager::store(&token)?;
+            let profile = TokenManager::profile_name()?;
+            TokenManager::store(&profile, token.as_str())?;
+            if let Some(team) = &args.team {
+                TokenManager::set_team(&profile, team)?;
+            }
 
             println!();
             println!("✓ Authentication successful!");
             println!();
-            println!("  Email:  {}", &user_info.email);
-            println!("  Handle: {}", &user_info.handle);
+            println!("  Profile: {profile}");
+            println!("  Email:   {}", &user_info.email);
+            println!("  Handle:  {}", &user_info.handle);
             println!();
-            println!("Token stored in ~/.config/figma-cli/config.json");
+            println!("Token stored in {}", TokenManager::path()?.display());
         }
 
         AuthCommand::Test => {
-            let token = TokenManager::get()?
-                .context("No authentication token found. Run 'figma-cli auth login' first")?;
-
-            let client = FigmaClient::new(token)?;
-            let user_info = client
+            let ctx = ClientContext::new(None)?;
+            let user_info = ctx
+                .client
                 .validate_auth()
                 .await
                 .context("Authentication test failed")?;
@@ -133,21 +549,143 @@ pub async fn handle_auth(command: AuthCommand) -> Result<()> {
             println!();
             println!("✓ Authentication valid");
             println!();
-            println!("  Email:  {}", &user_info.email);
-            println!("  Handle: {}", &user_info.handle);
+            println!("  Profile: {}", ctx.profile);
+            println!("  Email:   {}", &user_info.email);
+            println!("  Handle:  {}", &user_info.handle);
         }
 
         AuthCommand::Logout => {
-            TokenManager::delete()?;
+            let profile = TokenManager::profile_name()?;
+            TokenManager::delete(&profile)?;
             println!();
             println!("✓ Logged out successfully");
-            println!("Token removed from config file.");
+            println!("Stored credentials for profile '{profile}' removed.");
+        }
+
+        AuthCommand::List { json } => {
+            let active = TokenManager::profile_name()?;
+            let profiles = TokenManager::list()?;
+
+            if json {
+                let profiles: Vec<_> = profiles
+                    .iter()
+                    .map(|p| {
+                        serde_json::json!({
+                            "name": p.name,
+                            "active": p.name == active,
+                            "kind": credential_kind(&p.credential),
+                            "team": p.team,
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&profiles)?);
+            } else if profiles.is_empty() {
+                println!();
+                println!("No profiles. Run 'figma-cli auth login' first");
+                println!();
+            } else {
+                println!();
+                println!("Profiles:");
+                println!();
+                for p in &profiles {
+                    let marker = if p.name == active { "*" } else { " " };
+                    let team = p
+                        .team
+                        .as_deref()
+                        .map(|t| format!("  team {t}"))
+                        .unwrap_or_default();
+                    println!(
+                        "  {marker} {:<16} {}{team}",
+                        p.name,
+                        credential_kind(&p.credential)
+                    );
+                }
+                println!();
+            }
+        }
+
+        AuthCommand::Use { profile } => {
+            TokenManager::activate(&profile)?;
+            println!();
+            println!("✓ Switched to profile '{profile}'");
         }
     }
 
     Ok(())
 }
 
+/// Authorization-code login with PKCE, redirecting back to a local listener
+async fn oauth_login(args: AuthLoginArgs) -> Result<()> {
+    let client_id = args
+        .client_id
+        .context("--oauth needs the app's --client-id (or FIGMA_CLIENT_ID)")?;
+    let client_secret = args
+        .client_secret
+        .context("--oauth needs the app's --client-secret (or FIGMA_CLIENT_SECRET)")?;
+    let config = Config::load()?;
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", args.port))
+        .await
+        .with_context(|| format!("Failed to listen on port {}", args.port))?;
+    let redirect_uri = format!("http://localhost:{}/callback", args.port);
+
+    let pkce = Pkce::generate()?;
+    let state = random_token()?;
+    let url = authorization_url(&client_id, &redirect_uri, &args.scopes, &state, &pkce);
+
+    println!("Open this URL to authorize figma-cli:");
+    println!();
+    println!("  {url}");
+    println!();
+    if !args.no_browser {
+        open_browser(&url);
+    }
+
+    let code = wait_for_code(listener, state).await?;
+
+    let token = exchange_code(
+        &reqwest::Client::new(),
+        &config.http.base_url,
+        &client_id,
+        &client_secret,
+        &code,
+        &redirect_uri,
+        &pkce.verifier,
+    )
+    .await
+    .context("Failed to exchange authorization code")?;
+
+    let client =
+        FigmaClient::new(Credential::OAuth(token.clone()))?.with_base_url(&config.http.base_url);
+    let user_info = client
+        .validate_auth()
+        .await
+        .context("Authentication failed")?;
+
+    let profile = TokenManager::profile_name()?;
+    TokenManager::store_oauth(&profile, &token)?;
+    if let Some(team) = &args.team {
+        TokenManager::set_team(&profile, team)?;
+    }
+
+    println!("✓ Authentication successful!");
+    println!();
+    println!("  Profile: {profile}");
+    println!("  Email:   {}", &user_info.email);
+    println!("  Handle:  {}", &user_info.handle);
+    println!(
+        "  Expires: {}",
+        token.expires_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    println!();
+    println!("Tokens stored in {}", TokenManager::path()?.display());
+    println!(
+        "Access is refreshed automatically; run 'figma-cli auth logout' to revoke it locally."
+    );
+
+    Ok(())
+}
+
 /// Handle config command
 pub async fn handle_config(command: ConfigCommand) -> Result<()> {
     match command {
@@ -207,6 +745,10 @@ fn handle_config_show(json: bool) -> Result<()> {
 
     println!();
     println!("  Extract depth: {}", config.extraction.depth);
+    println!("  API base URL: {}", config.http.base_url);
+    if let Some(team) = &config.team {
+        println!("  Default team: {team}");
+    }
     println!();
     println!("  Image format: {}", config.images.format);
     println!("  Image scale: {}", config.images.scale);
@@ -293,7 +835,7 @@ fn handle_config_edit(global: bool) -> Result<()> {
 fn handle_config_set(key: &str, value: &str) -> Result<()> {
     // Special case: token uses TokenManager
     if key == "token" {
-        TokenManager::store(value)?;
+        TokenManager::store(&TokenManager::profile_name()?, value)?;
         println!();
         println!("✓ Token updated");
         return Ok(());
@@ -328,8 +870,8 @@ fn handle_config_set(key: &str, value: &str) -> Result<()> {
 fn handle_config_get(key: &str) -> Result<()> {
     // Special case: token uses TokenManager
     if key == "token" {
-        match TokenManager::get()? {
-            Some(token) => println!("{}", mask_token(&token)),
+        match TokenManager::get(&TokenManager::profile_name()?)? {
+            Some(token) => println!("{}", mask_token(token.as_str())),
             None => println!("Not set"),
         }
         return Ok(());
@@ -387,9 +929,24 @@ fn confirm_overwrite(config_path: &std::path::Path) -> Result<bool> {
 
 /// Print token status with masking
 fn print_token_status() {
-    match TokenManager::get() {
-        Ok(Some(token)) => println!("  Token:  {}", mask_token(&token)),
-        _ => println!("  Token:  Not set"),
+    match TokenManager::profile_name().and_then(|profile| TokenManager::credential(&profile)) {
+        Ok(Some(Credential::Personal(token))) => {
+            println!("  Token:  {}", mask_token(token.as_str()));
+        }
+        Ok(Some(Credential::OAuth(token))) => println!(
+            "  Token:  OAuth (expires {})",
+            token.expires_at.format("%Y-%m-%d %H:%M:%S UTC")
+        ),
+        Ok(None) => println!("  Token:  Not set"),
+        Err(e) => println!("  Token:  {e}"),
+    }
+}
+
+/// How a profile authenticates
+const fn credential_kind(credential: &Credential) -> &'static str {
+    match credential {
+        Credential::Personal(_) => "token",
+        Credential::OAuth(_) => "oauth",
     }
 }
 
@@ -483,6 +1040,9 @@ fn get_toml_value(value: &toml::Value, path: &[&str]) -> Result<String> {
     Ok(result)
 }
 
+/// Top-level string settings that `config set` may add
+const OPTIONAL_KEYS: &[&str] = &["team"];
+
 /// Set value in TOML using dot-separated path
 fn set_toml_value(value: &mut toml::Value, path: &[&str], value_str: &str) -> Result<()> {
     if path.is_empty() {
@@ -495,11 +1055,12 @@ fn set_toml_value(value: &mut toml::Value, path: &[&str], value_str: &str) -> Re
             .as_table_mut()
             .ok_or_else(|| anyhow::anyhow!("Root is not a table"))?;
 
-        let existing = table
-            .get(path[0])
-            .ok_or_else(|| anyhow::anyhow!("Key not found: {}", path[0]))?;
-
-        let new_value = parse_toml_value(existing, value_str)?;
+        // Optional keys are absent from the file until first set
+        let new_value = match table.get(path[0]) {
+            Some(existing) => parse_toml_value(existing, value_str)?,
+            None if OPTIONAL_KEYS.contains(&path[0]) => toml::Value::String(value_str.to_string()),
+            None => anyhow::bail!("Key not found: {}", path[0]),
+        };
         table.insert(path[0].to_string(), new_value);
     } else {
         // Nested key
@@ -594,7 +1155,12 @@ pub async fn handle_inspect(args: InspectArgs) -> Result<()> {
     // Fetch nodes
     let nodes_response = ctx
         .client
-        .get_nodes(&file_key, &node_ids, Some(args.depth))
+        .get_nodes(
+            &file_key,
+            &node_ids,
+            Some(args.depth),
+            args.at_version.as_deref(),
+        )
         .await
         .context("Failed to fetch nodes from Figma")?;
 
@@ -606,6 +1172,7 @@ pub async fn handle_inspect(args: InspectArgs) -> Result<()> {
         },
         "nodes": nodes_response.nodes,
         "depth": args.depth,
+        "version": args.at_version,
     });
 
     // Output results
@@ -640,23 +1207,22 @@ pub async fn handle_images(args: ImagesArgs) -> Result<()> {
 
     let mut ctx = ClientContext::new(args.config.as_deref())?;
 
+    if args.fills {
+        let node_ids = args
+            .frames
+            .as_deref()
+            .map(normalize_node_ids)
+            .unwrap_or(url_node_ids);
+        return handle_image_fills(ctx, &file_key, &node_ids, &args).await;
+    }
+
     // Override config with CLI args
     ctx.config.images.format = args.format.clone();
     ctx.config.images.scale = args.scale as f32;
 
     // Determine frame IDs
-    let frame_ids = if let Some(frames) = args.frames {
-        // Convert hyphenated format to colon format if needed
-        frames
-            .iter()
-            .map(|id| {
-                if let Some(converted) = crate::utils::parse_node_id_from_url(id) {
-                    converted
-                } else {
-                    id.clone()
-                }
-            })
-            .collect()
+    let frame_ids = if let Some(frames) = &args.frames {
+        normalize_node_ids(frames)
     } else if !url_node_ids.is_empty() {
         // Use node IDs from URL
         url_node_ids
@@ -669,9 +1235,9 @@ pub async fn handle_images(args: ImagesArgs) -> Result<()> {
     }
 
     // Process images
-    let processor = ImageProcessor::new(ctx.config.images.clone())?;
+    let processor = ImageProcessor::new(ctx.config.images.clone());
     let results = processor
-        .process_frames(&ctx.token, &file_key, &frame_ids, args.base64)
+        .process_frames(&ctx.client, &file_key, &frame_ids, args.base64)
         .await?;
 
     // Convert to AI format
@@ -711,6 +1277,52 @@ pub async fn handle_images(args: ImagesArgs) -> Result<()> {
     Ok(())
 }
 
+/// Convert hyphenated node IDs from URLs to the API's colon format
+fn normalize_node_ids(ids: &[String]) -> Vec<String> {
+    ids.iter()
+        .map(|id| crate::utils::parse_node_id_from_url(id).unwrap_or_else(|| id.clone()))
+        .collect()
+}
+
+/// Download original image fills and write their manifest
+async fn handle_image_fills(
+    ctx: ClientContext,
+    file_key: &str,
+    node_ids: &[String],
+    args: &ImagesArgs,
+) -> Result<()> {
+    let image_dir = std::path::Path::new(&args.image_dir);
+    let orchestrator = Orchestrator::new(ctx.client);
+    let manifest = orchestrator
+        .download_image_fills(file_key, node_ids, image_dir, args.max_concurrent)
+        .await
+        .context("Failed to download image fills")?;
+
+    let manifest_path = args
+        .output
+        .as_ref()
+        .map_or_else(|| image_dir.join("manifest.json"), std::path::PathBuf::from);
+    let json = if args.pretty {
+        serde_json::to_string_pretty(&manifest)?
+    } else {
+        serde_json::to_string(&manifest)?
+    };
+    std::fs::write(&manifest_path, json)?;
+
+    let downloaded = manifest
+        .images
+        .iter()
+        .filter(|image| image.local_path.is_some())
+        .count();
+    println!();
+    println!("✓ Image fills downloaded");
+    println!("  Images: {downloaded}/{}", manifest.total);
+    println!("  Directory: {}", image_dir.display());
+    println!("  Manifest: {}", manifest_path.display());
+
+    Ok(())
+}
+
 pub async fn handle_query(args: QueryArgs) -> Result<()> {
     let (file_key, url_node_ids) = crate::utils::parse_file_and_nodes_from_url(&args.file)
         .map_err(|e| anyhow::anyhow!("Failed to parse file/node IDs: {e}"))?;
@@ -721,14 +1333,14 @@ pub async fn handle_query(args: QueryArgs) -> Result<()> {
         if !node_ids.is_empty() {
             let response = ctx
                 .client
-                .get_nodes(&file_key, node_ids, args.depth)
+                .get_nodes(&file_key, node_ids, args.depth, args.at_version.as_deref())
                 .await
                 .context("Failed to fetch nodes")?;
             serde_json::to_value(&response)?
         } else {
             let file = ctx
                 .client
-                .get_file(&file_key, args.depth)
+                .get_file(&file_key, args.depth, args.at_version.as_deref())
                 .await
                 .context("Failed to fetch file")?;
             serde_json::to_value(&file)?
@@ -736,7 +1348,7 @@ pub async fn handle_query(args: QueryArgs) -> Result<()> {
     } else {
         let file = ctx
             .client
-            .get_file(&file_key, args.depth)
+            .get_file(&file_key, args.depth, args.at_version.as_deref())
             .await
             .context("Failed to fetch file")?;
         serde_json::to_value(&file)?
@@ -762,9 +1374,433 @@ pub async fn handle_query(args: QueryArgs) -> Result<()> {
     Ok(())
 }
 
+/// Handle versions command
+pub async fn handle_versions(args: VersionsArgs) -> Result<()> {
+    let file_key = crate::utils::parse_file_key_from_url(&args.file)
+        .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+
+    let ctx = ClientContext::new(args.config.as_deref())?;
+
+    let versions = ctx
+        .client
+        .list_versions(&file_key, args.limit)
+        .await
+        .context("Failed to fetch version history")?;
+
+    let output_json = serde_json::json!({
+        "file": { "key": file_key },
+        "versions": versions,
+        "total": versions.len(),
+    });
+
+    let output_str = if args.pretty {
+        serde_json::to_string_pretty(&output_json)?
+    } else {
+        serde_json::to_string(&output_json)?
+    };
+
+    if let Some(output_path) = args.output {
+        std::fs::write(&output_path, output_str)?;
+        println!();
+        println!("✓ Versions listed");
+        println!("  File: {output_path}");
+        println!("  Total versions: {}", versions.len());
+    } else {
+        println!("{output_str}");
+    }
+
+    Ok(())
+}
+
+/// Handle branches command
+pub async fn handle_branches(args: BranchesArgs) -> Result<()> {
+    let (file_key, _) = crate::utils::parse_file_url(&args.file)
+        .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+
+    let ctx = ClientContext::new(args.config.as_deref())?;
+
+    let file = ctx
+        .client
+        .get_branches(&file_key)
+        .await
+        .context("Failed to fetch branches")?;
+
+    let branches: Vec<ListedBranch> = file
+        .branches
+        .into_iter()
+        .map(|b| ListedBranch {
+            key: b.key,
+            name: b.name,
+            last_modified: b.last_modified,
+            thumbnail_url: b.thumbnail_url,
+            link_url: b.link_url,
+        })
+        .collect();
+    let listing = BranchListing {
+        file_key,
+        file_name: file.name,
+        total: branches.len(),
+        branches,
+    };
+
+    format_branches_output(&listing, args.format, args.output.as_deref(), args.pretty)
+}
+
+/// Handle projects command
+pub async fn handle_projects(args: ProjectsArgs) -> Result<()> {
+    let ctx = ClientContext::new(args.config.as_deref())?;
+    let team_id = resolve_team(args.team.as_deref(), &ctx.config)?;
+
+    let team = ctx
+        .client
+        .get_team_projects(&team_id)
+        .await
+        .context("Failed to fetch team projects")?;
+
+    let projects: Vec<ListedProject> = team
+        .projects
+        .into_iter()
+        .map(|p| ListedProject {
+            id: p.id,
+            name: p.name,
+        })
+        .collect();
+    let listing = ProjectListing {
+        team_id,
+        team_name: team.name,
+        total: projects.len(),
+        projects,
+    };
+
+    format_projects_output(&listing, args.format, args.output.as_deref(), args.pretty)
+}
+
+/// Handle files command
+pub async fn handle_files(args: FilesArgs) -> Result<()> {
+    let ctx = ClientContext::new(args.config.as_deref())?;
+
+    let projects = if let Some(project) = &args.project {
+        let project_id = crate::utils::parse_project_id(project)
+            .map_err(|e| anyhow::anyhow!("Failed to parse project ID: {e}"))?;
+        vec![project_id]
+    } else {
+        let team_id = resolve_team(args.team.as_deref(), &ctx.config)?;
+        ctx.client
+            .get_team_projects(&team_id)
+            .await
+            .context("Failed to fetch team projects")?
+            .projects
+            .into_iter()
+            .map(|p| p.id)
+            .collect()
+    };
+
+    let mut files = Vec::new();
+    for project_id in projects {
+        let project = ctx
+            .client
+            .get_project_files(&project_id)
+            .await
+            .with_context(|| format!("Failed to fetch files of project {project_id}"))?;
+
+        files.extend(project.files.into_iter().map(|f| ListedFile {
+            key: f.key,
+            name: f.name,
+            last_modified: f.last_modified,
+            thumbnail_url: f.thumbnail_url,
+            project_id: project_id.clone(),
+            project_name: project.name.clone(),
+        }));
+    }
+
+    let listing = FileListing {
+        total: files.len(),
+        files,
+    };
+
+    format_files_output(&listing, args.format, args.output.as_deref(), args.pretty)
+}
+
+/// Team from `--team`, falling back to the configured default
+fn resolve_team(team: Option<&str>, config: &Config) -> Result<String> {
+    let team = team.or(config.team.as_deref()).context(
+        "No team specified. Pass --team or set a default with 'figma-cli config set team <ID>'",
+    )?;
+
+    crate::utils::parse_team_id(team).map_err(|e| anyhow::anyhow!("Failed to parse team ID: {e}"))
+}
+
+/// Handle diff command
+pub async fn handle_diff(args: DiffArgs) -> Result<()> {
+    if args.from.is_none() && args.from_snapshot.is_none() {
+        anyhow::bail!("Specify the older side with --from <VERSION> or --from-snapshot <PATH>");
+    }
+
+    let file_key = args
+        .file
+        .as_deref()
+        .map(crate::utils::parse_file_key_from_url)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+
+    let needs_api = args.from_snapshot.is_none() || args.to_snapshot.is_none();
+    let ctx = if needs_api {
+        Some(ClientContext::new(args.config.as_deref())?)
+    } else {
+        None
+    };
+
+    let old = load_diff_side(
+        ctx.as_ref(),
+        file_key.as_deref(),
+        args.from.as_deref(),
+        args.from_snapshot.as_deref(),
+    )
+    .await?;
+    let new = load_diff_side(
+        ctx.as_ref(),
+        file_key.as_deref(),
+        args.to.as_deref(),
+        args.to_snapshot.as_deref(),
+    )
+    .await?;
+
+    let diff = diff_files(&old, &new);
+
+    format_diff_output(&diff, args.format, args.output.as_deref(), args.pretty)?;
+
+    Ok(())
+}
+
+/// Fetch one side of a diff, from a snapshot file or from the API
+async fn load_diff_side(
+    ctx: Option<&ClientContext>,
+    file_key: Option<&str>,
+    version: Option<&str>,
+    snapshot: Option<&str>,
+) -> Result<FigmaFile> {
+    if let Some(path) = snapshot {
+        return load_snapshot(path);
+    }
+
+    let (Some(ctx), Some(file_key)) = (ctx, file_key) else {
+        anyhow::bail!("A file key is required unless both sides are snapshots");
+    };
+
+    ctx.client
+        .get_file(file_key, None, version)
+        .await
+        .with_context(|| match version {
+            Some(v) => format!("Failed to fetch version {v}"),
+            None => "Failed to fetch latest version".to_string(),
+        })
+}
+
+/// Read a file saved with `query FILE @` or copied out of the cache directory
+fn load_snapshot(path: &str) -> Result<FigmaFile> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read snapshot {path}"))?;
+    let mut value: serde_json::Value =
+        serde_json::from_str(&content).with_context(|| format!("Invalid JSON in {path}"))?;
+
+    // Cache entries wrap the file in `data`
+    if let Some(data) = value.get_mut("data").map(serde_json::Value::take) {
+        value = data;
+    }
+    let file_key = value
+        .get("fileKey")
+        .and_then(|k| k.as_str())
+        .map(str::to_string);
+
+    let mut file: FigmaFile = serde_json::from_value(value)
+        .with_context(|| format!("{path} is not a Figma file snapshot"))?;
+    if let Some(key) = file_key {
+        file.file_key = key;
+    }
+
+    Ok(file)
+}
+
+/// Handle comments command
+pub async fn handle_comments(command: CommentsCommand) -> Result<()> {
+    match command {
+        CommentsCommand::List(args) => handle_comments_list(args).await,
+        CommentsCommand::Post(args) => handle_comments_post(args).await,
+        CommentsCommand::Reply(args) => handle_comments_reply(args).await,
+        CommentsCommand::Delete(args) => handle_comments_delete(args).await,
+    }
+}
+
+async fn handle_comments_list(args: CommentsListArgs) -> Result<()> {
+    let file_key = crate::utils::parse_file_key_from_url(&args.file)
+        .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+
+    let mut filter = CommentFilter::new();
+    match args.status {
+        CommentStatus::All => {}
+        CommentStatus::Open => filter = filter.with_resolved(false),
+        CommentStatus::Resolved => filter = filter.with_resolved(true),
+    }
+    if let Some(author) = args.author {
+        filter = filter.with_author(author);
+    }
+    if let Some(since) = &args.since {
+        filter = filter.with_since(parse_date_bound(since, false)?);
+    }
+    if let Some(until) = &args.until {
+        filter = filter.with_until(parse_date_bound(until, true)?);
+    }
+
+    let ctx = ClientContext::new(args.config.as_deref())?;
+
+    let comments = ctx
+        .client
+        .get_comments(&file_key)
+        .await
+        .context("Failed to fetch comments")?;
+    // The file tree is only needed to name the nodes comments are pinned to
+    let file = ctx
+        .client
+        .get_file(&file_key, None, None)
+        .await
+        .context("Failed to fetch file")?;
+
+    let report = build_report(&file, comments, &filter);
+
+    format_comments_output(&report, args.format, args.output.as_deref(), args.pretty)?;
+
+    Ok(())
+}
+
+async fn handle_comments_post(args: CommentsPostArgs) -> Result<()> {
+    let (file_key, url_node_ids) = crate::utils::parse_file_and_nodes_from_url(&args.file)
+        .map_err(|e| anyhow::anyhow!("Failed to parse file/node IDs: {e}"))?;
+
+    let node_id = args
+        .node
+        .map(|id| crate::utils::parse_node_id_from_url(&id).unwrap_or(id))
+        .or_else(|| url_node_ids.into_iter().next());
+
+    let mut comment = NewComment::new(args.message);
+    if let Some(node_id) = node_id {
+        comment = comment.with_node(node_id);
+    }
+
+    send_comment(
+        &file_key,
+        &comment,
+        args.dry_run,
+        args.pretty,
+        args.config.as_deref(),
+    )
+    .await
+}
+
+async fn handle_comments_reply(args: CommentsReplyArgs) -> Result<()> {
+    let file_key = crate::utils::parse_file_key_from_url(&args.file)
+        .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+
+    let comment = NewComment::new(args.message).with_reply_to(args.comment_id);
+
+    send_comment(
+        &file_key,
+        &comment,
+        args.dry_run,
+        args.pretty,
+        args.config.as_deref(),
+    )
+    .await
+}
+
+async fn send_comment(
+    file_key: &str,
+    comment: &NewComment,
+    dry_run: bool,
+    pretty: bool,
+    config_path: Option<&str>,
+) -> Result<()> {
+    if dry_run {
+        let config = load_config(config_path)?;
+        let request = serde_json::json!({
+            "method": "POST",
+            "url": comments_url(&config.http.base_url, file_key, None),
+            "body": comment,
+        });
+        return print_json(&request, pretty);
+    }
+
+    let ctx = ClientContext::new(config_path)?;
+    let posted = ctx
+        .client
+        .post_comment(file_key, comment)
+        .await
+        .context("Failed to post comment")?;
+
+    print_json(&posted, pretty)
+}
+
+async fn handle_comments_delete(args: CommentsDeleteArgs) -> Result<()> {
+    let file_key = crate::utils::parse_file_key_from_url(&args.file)
+        .map_err(|e| anyhow::anyhow!("Failed to parse file key: {e}"))?;
+
+    if args.dry_run {
+        let config = load_config(args.config.as_deref())?;
+        let request = serde_json::json!({
+            "method": "DELETE",
+            "url": comments_url(&config.http.base_url, &file_key, Some(&args.comment_id)),
+        });
+        return print_json(&request, false);
+    }
+
+    let ctx = ClientContext::new(args.config.as_deref())?;
+    ctx.client
+        .delete_comment(&file_key, &args.comment_id)
+        .await
+        .context("Failed to delete comment")?;
+
+    print_json(
+        &serde_json::json!({ "id": args.comment_id, "deleted": true }),
+        false,
+    )
+}
+
+fn load_config(config_path: Option<&str>) -> Result<Config> {
+    match config_path {
+        Some(path) => Ok(Config::load_from(std::path::Path::new(path))?),
+        None => Ok(Config::load()?),
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T, pretty: bool) -> Result<()> {
+    let json = if pretty {
+        serde_json::to_string_pretty(value)?
+    } else {
+        serde_json::to_string(value)?
+    };
+    println!("{json}");
+    Ok(())
+}
+
+/// Parse a `--since`/`--until` value; a bare date used as an upper bound covers the whole day
+fn parse_date_bound(value: &str, end_of_day: bool) -> Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&chrono::Utc));
+    }
+
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date '{value}', expected YYYY-MM-DD or RFC 3339"))?;
+    let date = if end_of_day {
+        date.succ_opt().context("Date out of range")?
+    } else {
+        date
+    };
+
+    Ok(date.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
 pub async fn handle_cache(command: CacheCommand) -> Result<()> {
     let config = Config::load()?;
-    let cache_dir = config.cache_path();
+    let cache_dir = config.profile_cache_path(&TokenManager::profile_name()?);
     let cache = Cache::new(cache_dir.clone(), config.cache.ttl)?;
 
     match command {
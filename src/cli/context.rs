@@ -1,6 +1,6 @@
 //! Client context for command handlers
 
-use crate::client::{FigmaClient, TokenManager};
+use crate::client::{Credential, FigmaClient, RateLimits, Scheduler, TokenManager};
 use crate::core::{Cache, Config};
 use anyhow::{Context, Result};
 use std::path::Path;
@@ -9,32 +9,64 @@ use std::sync::Arc;
 pub struct ClientContext {
     pub config: Config,
     pub client: FigmaClient,
-    pub token: String,
+    pub cache: Arc<Cache>,
+    /// Profile whose login, team and cache are in use
+    pub profile: String,
 }
 
 impl ClientContext {
     pub fn new(config_path: Option<&str>) -> Result<Self> {
-        let config = match config_path {
+        let mut config = match config_path {
             Some(path) => Config::load_from(Path::new(path))?,
             None => Config::load()?,
         };
 
-        let token = config
+        let profile = TokenManager::profile_name()?;
+        let stored = TokenManager::load(&profile)?;
+        if let Some(team) = stored.as_ref().and_then(|p| p.team.clone()) {
+            config.team = Some(team);
+        }
+
+        // A token in a project or --config file is still honoured, after the stored credentials,
+        // unless a profile was picked explicitly
+        let fallback = config
             .token
             .clone()
-            .or_else(|| TokenManager::get().ok().flatten())
-            .context("No authentication token found. Run 'figma-cli auth login' first")?;
+            .filter(|_| !TokenManager::profile_selected());
+        let credential = TokenManager::credential(&profile)?
+            .or_else(|| fallback.map(Credential::Personal))
+            .with_context(|| {
+                format!(
+                    "No authentication token found for profile '{profile}'. Run 'figma-cli auth login' first"
+                )
+            })?;
 
-        let cache_dir = config.cache_path();
+        let cache_dir = config.profile_cache_path(&profile);
         let cache = Arc::new(Cache::new(cache_dir, config.cache.ttl)?);
 
-        let client =
-            FigmaClient::with_timeout(token.clone(), config.http.timeout)?.with_cache(cache);
+        let limits = &config.rate_limit;
+        Scheduler::init_global(RateLimits {
+            tier1_per_minute: limits.tier1_per_minute,
+            tier2_per_minute: limits.tier2_per_minute,
+            tier3_per_minute: limits.tier3_per_minute,
+            max_concurrent: limits.max_concurrent,
+        });
+
+        let refreshed_profile = profile.clone();
+        let client = FigmaClient::with_timeout(credential, config.http.timeout)?
+            .with_base_url(&config.http.base_url)
+            .with_cache(Arc::clone(&cache))
+            .with_refresh_hook(Arc::new(move |token| {
+                if let Err(e) = TokenManager::store_oauth(&refreshed_profile, token) {
+                    tracing::warn!("Failed to save refreshed token: {}", e);
+                }
+            }));
 
         Ok(Self {
             config,
             client,
-            token,
+            cache,
+            profile,
         })
     }
 }
@@ -1,14 +1,19 @@
 //! CLI interface
 
 pub mod args;
+pub mod batch;
 pub mod commands;
 mod context;
+mod oauth;
 pub mod output;
 pub mod summary;
+pub mod webhook_server;
 
 pub use args::{Cli, Commands, OutputFormat};
 pub use commands::{
-    handle_auth, handle_cache, handle_config, handle_extract, handle_images, handle_inspect,
-    handle_query,
+    handle_auth, handle_branches, handle_cache, handle_comments, handle_config, handle_diff,
+    handle_extract, handle_extract_batch, handle_files, handle_images, handle_inspect,
+    handle_projects, handle_query, handle_serve_webhooks, handle_versions, handle_watch,
+    handle_webhooks,
 };
 pub use output::format_output;
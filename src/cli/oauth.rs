@@ -0,0 +1,120 @@
+//! Browser side of `auth login --oauth`
+
+use super::webhook_server::respond;
+use anyhow::Result;
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, StatusCode};
+use hyper_util::rt::TokioIo;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+/// How long to wait for the browser to come back
+const LOGIN_TIMEOUT_SECS: u64 = 300;
+
+/// Outcome of the redirect: the authorization code, or why there is none
+type Callback = std::result::Result<String, String>;
+
+/// Open a URL in the default browser; failure just means the user opens it by hand
+pub fn open_browser(url: &str) {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+
+    let opened = std::process::Command::new(program)
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+    if let Err(e) = opened {
+        tracing::debug!("Failed to open browser: {}", e);
+    }
+}
+
+/// Serve `/callback` until Figma redirects back, returning the authorization code
+pub async fn wait_for_code(listener: TcpListener, state: String) -> Result<String> {
+    let (sender, mut receiver) = mpsc::channel::<Callback>(1);
+    let state = Arc::new(state);
+
+    let server = tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+
+            let sender = sender.clone();
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let outcome = callback(&request, &state);
+                    let sender = sender.clone();
+                    async move {
+                        let response = match &outcome {
+                            Some(Ok(_)) => {
+                                respond(StatusCode::OK, "Logged in. You can close this window.")
+                            }
+                            Some(Err(_)) => respond(StatusCode::BAD_REQUEST, "Login failed."),
+                            None => respond(StatusCode::NOT_FOUND, "Not found."),
+                        };
+                        if let Some(outcome) = outcome {
+                            let _ = sender.send(outcome).await;
+                        }
+                        Ok::<_, Infallible>(response)
+                    }
+                });
+
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    tracing::debug!("Connection error: {}", e);
+                }
+            });
+        }
+    });
+
+    let outcome =
+        tokio::time::timeout(Duration::from_secs(LOGIN_TIMEOUT_SECS), receiver.recv()).await;
+    server.abort();
+
+    match outcome {
+        Ok(Some(Ok(code))) => Ok(code),
+        Ok(Some(Err(reason))) => anyhow::bail!("Authorization failed: {reason}"),
+        Ok(None) => anyhow::bail!("Authorization failed: redirect listener stopped"),
+        Err(_) => anyhow::bail!("Timed out waiting for authorization"),
+    }
+}
+
+/// `None` for requests other than the redirect, e.g. a browser asking for a favicon
+fn callback(request: &Request<Incoming>, state: &str) -> Option<Callback> {
+    if request.uri().path() != "/callback" {
+        return None;
+    }
+
+    let params: HashMap<String, String> =
+        url::form_urlencoded::parse(request.uri().query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect();
+
+    let outcome = if let Some(error) = params.get("error") {
+        Err(error.clone())
+    } else if params.get("state").map(String::as_str) != Some(state) {
+        Err("state does not match".to_string())
+    } else {
+        params
+            .get("code")
+            .cloned()
+            .ok_or_else(|| "no authorization code".to_string())
+    };
+
+    Some(outcome)
+}
@@ -1,5 +1,9 @@
-use crate::cli::args::OutputFormat;
-use crate::models::extraction::ExtractionResult;
+use crate::cli::args::{CommentFormat, DiffFormat, ListFormat, OutputFormat};
+use crate::models::comment::{CommentReport, CommentThread};
+use crate::models::diff::{Change, FileDiff, NodeDiff, NodeStatus};
+use crate::models::document::{Color, Paint, TypeStyle};
+use crate::models::extraction::{Dimensions, ExtractionResult};
+use crate::models::project::{BranchListing, FileListing, ProjectListing};
 use anyhow::Result;
 use std::fmt::Write as _;
 use std::fs;
@@ -17,6 +21,122 @@ pub fn format_output(
         OutputFormat::Summary => super::summary::format_summary(result),
     };
 
+    write_output(&formatted, output_path)
+}
+
+pub fn format_diff_output(
+    diff: &FileDiff,
+    format: DiffFormat,
+    output_path: Option<&str>,
+    pretty: bool,
+) -> Result<()> {
+    let formatted = match format {
+        DiffFormat::Json if pretty => serde_json::to_string_pretty(diff)?,
+        DiffFormat::Json => serde_json::to_string(diff)?,
+        DiffFormat::Markdown => format_diff_markdown(diff),
+        DiffFormat::Text => format_diff_text(diff),
+    };
+
+    write_output(&formatted, output_path)
+}
+
+pub fn format_comments_output(
+    report: &CommentReport,
+    format: CommentFormat,
+    output_path: Option<&str>,
+    pretty: bool,
+) -> Result<()> {
+    let formatted = match format {
+        CommentFormat::Json if pretty => serde_json::to_string_pretty(report)?,
+        CommentFormat::Json => serde_json::to_string(report)?,
+        CommentFormat::Markdown => format_comments_markdown(report),
+        CommentFormat::Csv => format_comments_csv(report),
+    };
+
+    write_output(&formatted, output_path)
+}
+
+pub fn format_projects_output(
+    listing: &ProjectListing,
+    format: ListFormat,
+    output_path: Option<&str>,
+    pretty: bool,
+) -> Result<()> {
+    let formatted = match format {
+        ListFormat::Json if pretty => serde_json::to_string_pretty(listing)?,
+        ListFormat::Json => serde_json::to_string(listing)?,
+        ListFormat::Table => format_table(
+            &["ID", "NAME"],
+            listing
+                .projects
+                .iter()
+                .map(|p| vec![p.id.clone(), p.name.clone()])
+                .collect(),
+        ),
+    };
+
+    write_output(&formatted, output_path)
+}
+
+pub fn format_files_output(
+    listing: &FileListing,
+    format: ListFormat,
+    output_path: Option<&str>,
+    pretty: bool,
+) -> Result<()> {
+    let formatted = match format {
+        ListFormat::Json if pretty => serde_json::to_string_pretty(listing)?,
+        ListFormat::Json => serde_json::to_string(listing)?,
+        ListFormat::Table => format_table(
+            &["KEY", "NAME", "PROJECT", "LAST MODIFIED", "THUMBNAIL"],
+            listing
+                .files
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.key.clone(),
+                        f.name.clone(),
+                        f.project_name.clone(),
+                        f.last_modified.format("%Y-%m-%d %H:%M").to_string(),
+                        f.thumbnail_url.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        ),
+    };
+
+    write_output(&formatted, output_path)
+}
+
+pub fn format_branches_output(
+    listing: &BranchListing,
+    format: ListFormat,
+    output_path: Option<&str>,
+    pretty: bool,
+) -> Result<()> {
+    let formatted = match format {
+        ListFormat::Json if pretty => serde_json::to_string_pretty(listing)?,
+        ListFormat::Json => serde_json::to_string(listing)?,
+        ListFormat::Table => format_table(
+            &["KEY", "NAME", "LAST MODIFIED"],
+            listing
+                .branches
+                .iter()
+                .map(|b| {
+                    vec![
+                        b.key.clone(),
+                        b.name.clone(),
+                        b.last_modified.format("%Y-%m-%d %H:%M").to_string(),
+                    ]
+                })
+                .collect(),
+        ),
+    };
+
+    write_output(&formatted, output_path)
+}
+
+fn write_output(formatted: &str, output_path: Option<&str>) -> Result<()> {
     if let Some(path) = output_path {
         fs::write(path, formatted)?;
         tracing::info!("Output written to: {}", path);
@@ -73,6 +193,39 @@ fn format_text(result: &ExtractionResult) -> String {
         output.push_str("\n\n");
     }
 
+    if let Some(elements) = &result.elements {
+        output.push_str("Design Elements:\n");
+        output.push_str(&"=".repeat(80));
+        output.push_str("\n\n");
+
+        for element in elements {
+            let _ = writeln!(output, "{} {}", element.element_type, element.name);
+            let _ = writeln!(output, "Path: {}", element.path.to_path_string());
+            let _ = writeln!(output, "Node ID: {}", element.id);
+            let _ = writeln!(output, "Size: {}", format_dimensions(element.dimensions));
+            let _ = write!(output, "Children: {}\n\n", element.child_count);
+            output.push_str(&"-".repeat(80));
+            output.push_str("\n\n");
+        }
+    }
+
+    if let Some(images) = &result.images {
+        output.push_str("Images:\n");
+        output.push_str(&"=".repeat(80));
+        output.push_str("\n\n");
+
+        for image in images {
+            let _ = writeln!(output, "Path: {}", image.path.to_path_string());
+            let _ = writeln!(output, "Node ID: {}", image.node_id);
+            let _ = writeln!(output, "Size: {}", format_dimensions(image.dimensions));
+            let _ = write!(
+                output,
+                "File: {}\n\n",
+                image.local_path.as_deref().unwrap_or(&image.image_url)
+            );
+        }
+    }
+
     output
 }
 
@@ -132,9 +285,438 @@ fn format_markdown(result: &ExtractionResult) -> String {
         output.push_str("---\n\n");
     }
 
+    if let Some(elements) = &result.elements {
+        output.push_str("## Design Elements\n\n");
+        output.push_str("| Type | Name | Path | Size | Children |\n");
+        output.push_str("|------|------|------|------|----------|\n");
+        for element in elements {
+            let _ = writeln!(
+                output,
+                "| {} | {} | {} | {} | {} |",
+                element.element_type,
+                escape_table_cell(&element.name),
+                escape_table_cell(&element.path.to_path_string()),
+                format_dimensions(element.dimensions),
+                element.child_count
+            );
+        }
+        output.push('\n');
+    }
+
+    if let Some(images) = &result.images {
+        output.push_str("## Images\n\n");
+        for image in images {
+            let location = image.local_path.as_deref().unwrap_or(&image.image_url);
+            let _ = writeln!(
+                output,
+                "- **{}** ({}): [{location}]({location})",
+                image.node_name,
+                format_dimensions(image.dimensions)
+            );
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Unified-diff style view: one hunk per node, `-` for the old side and `+` for the new
+fn format_diff_text(diff: &FileDiff) -> String {
+    let mut output = String::new();
+
+    let _ = writeln!(output, "--- {} @ {}", diff.file_name, diff.from.version);
+    let _ = writeln!(output, "+++ {} @ {}", diff.file_name, diff.to.version);
+
+    for node in &diff.nodes {
+        let _ = writeln!(output, "@@ {} ({}) @@", node.location(), node.node_id);
+
+        match node.status {
+            NodeStatus::Added | NodeStatus::Removed => {
+                let (sign, verb) = if node.status == NodeStatus::Added {
+                    ('+', "added")
+                } else {
+                    ('-', "removed")
+                };
+                let _ = write!(output, "{sign}{} {verb}", node.node_type);
+                if node.descendants > 0 {
+                    let _ = write!(output, " ({} nested nodes)", node.descendants);
+                }
+                output.push('\n');
+                if let Some(characters) = &node.characters {
+                    push_lines(&mut output, sign, characters);
+                }
+            }
+            NodeStatus::Changed => {
+                for change in &node.changes {
+                    let (from, to) = match change {
+                        Change::Characters { from, to } => {
+                            push_lines(&mut output, '-', from);
+                            push_lines(&mut output, '+', to);
+                            continue;
+                        }
+                        Change::Moved { from, to } => (
+                            format!("path: {}", from.to_path_string()),
+                            format!("path: {}", to.to_path_string()),
+                        ),
+                        Change::Renamed { from, to } => {
+                            (format!("name: {from}"), format!("name: {to}"))
+                        }
+                        Change::Fills { from, to } => (
+                            format!("fills: {}", format_paints(from)),
+                            format!("fills: {}", format_paints(to)),
+                        ),
+                        Change::Size { from, to } => (
+                            format!("size: {}", format_dimensions(*from)),
+                            format!("size: {}", format_dimensions(*to)),
+                        ),
+                        Change::Style { from, to } => {
+                            let (from, to) = style_delta(from.as_deref(), to.as_deref());
+                            (format!("style: {from}"), format!("style: {to}"))
+                        }
+                    };
+                    let _ = writeln!(output, "-{from}");
+                    let _ = writeln!(output, "+{to}");
+                }
+            }
+        }
+    }
+
+    output
+}
+
+fn format_diff_markdown(diff: &FileDiff) -> String {
+    let mut output = String::new();
+
+    let _ = write!(output, "# Diff: {}\n\n", diff.file_name);
+    let _ = writeln!(
+        output,
+        "**From:** {} ({})",
+        diff.from.version, diff.from.last_modified
+    );
+    let _ = write!(
+        output,
+        "**To:** {} ({})\n\n",
+        diff.to.version, diff.to.last_modified
+    );
+
+    let summary = &diff.summary;
+    output.push_str("| Added | Removed | Moved | Renamed | Text | Fills | Size | Style |\n");
+    output.push_str("|-------|---------|-------|---------|------|-------|------|-------|\n");
+    let _ = write!(
+        output,
+        "| {} | {} | {} | {} | {} | {} | {} | {} |\n\n",
+        summary.added,
+        summary.removed,
+        summary.moved,
+        summary.renamed,
+        summary.characters,
+        summary.fills,
+        summary.size,
+        summary.style
+    );
+
+    for (status, title) in [
+        (NodeStatus::Added, "Added"),
+        (NodeStatus::Removed, "Removed"),
+        (NodeStatus::Changed, "Changed"),
+    ] {
+        let nodes: Vec<&NodeDiff> = diff.nodes.iter().filter(|n| n.status == status).collect();
+        if nodes.is_empty() {
+            continue;
+        }
+
+        let _ = write!(output, "## {title}\n\n");
+        for node in nodes {
+            let _ = write!(
+                output,
+                "- **{}** {} (`{}`)",
+                node.node_type,
+                node.location(),
+                node.node_id
+            );
+            if node.descendants > 0 {
+                let _ = write!(output, " with {} nested nodes", node.descendants);
+            }
+            output.push('\n');
+            if let Some(characters) = &node.characters {
+                let _ = writeln!(output, "  - Text: {characters:?}");
+            }
+            for change in &node.changes {
+                let line = match change {
+                    Change::Moved { from, to } => {
+                        format!("Moved: {} → {}", from.to_path_string(), to.to_path_string())
+                    }
+                    Change::Renamed { from, to } => format!("Renamed: {from:?} → {to:?}"),
+                    Change::Characters { from, to } => format!("Text: {from:?} → {to:?}"),
+                    Change::Fills { from, to } => {
+                        format!("Fills: {} → {}", format_paints(from), format_paints(to))
+                    }
+                    Change::Size { from, to } => format!(
+                        "Size: {} → {}",
+                        format_dimensions(*from),
+                        format_dimensions(*to)
+                    ),
+                    Change::Style { from, to } => {
+                        let (from, to) = style_delta(from.as_deref(), to.as_deref());
+                        format!("Style: {from} → {to}")
+                    }
+                };
+                let _ = writeln!(output, "  - {line}");
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn format_comments_markdown(report: &CommentReport) -> String {
+    let mut output = String::new();
+
+    let _ = write!(output, "# Comments: {}\n\n", report.file_name);
+    let _ = write!(output, "{} threads\n\n", report.threads.len());
+
+    for thread in &report.threads {
+        output.push_str("## ");
+        if let Some(number) = &thread.number {
+            let _ = write!(output, "#{number} ");
+        }
+        let _ = write!(
+            output,
+            "{} · {}",
+            thread.author,
+            thread.created_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        if thread.resolved_at.is_some() {
+            output.push_str(" · Resolved");
+        }
+        output.push_str("\n\n");
+
+        if let Some(location) = comment_location(thread) {
+            let _ = write!(output, "**On:** {location}\n\n");
+        }
+
+        for line in thread.message.lines() {
+            let _ = writeln!(output, "> {line}");
+        }
+        output.push('\n');
+
+        for reply in &thread.replies {
+            let _ = writeln!(
+                output,
+                "- **{}** ({}): {}",
+                reply.author,
+                reply.created_at.format("%Y-%m-%d %H:%M UTC"),
+                reply.message.replace('\n', " ")
+            );
+        }
+        if !thread.replies.is_empty() {
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/// One row per comment; replies carry their thread id as `parent_id`
+fn format_comments_csv(report: &CommentReport) -> String {
+    let mut output = String::from(
+        "thread_id,comment_id,parent_id,author,created_at,resolved_at,node_id,node_name,path,message",
+    );
+
+    for thread in &report.threads {
+        let anchor = thread.anchor.as_ref();
+        let node_id = anchor.map_or("", |a| a.node_id.as_str());
+        let node_name = anchor.and_then(|a| a.name.as_deref()).unwrap_or_default();
+        let path = anchor
+            .and_then(|a| a.path.as_ref())
+            .map(|p| p.to_path_string())
+            .unwrap_or_default();
+        let resolved_at = thread
+            .resolved_at
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default();
+
+        push_csv_row(
+            &mut output,
+            &[
+                &thread.id,
+                &thread.id,
+                "",
+                &thread.author,
+                &thread.created_at.to_rfc3339(),
+                &resolved_at,
+                node_id,
+                node_name,
+                &path,
+                &thread.message,
+            ],
+        );
+        for reply in &thread.replies {
+            push_csv_row(
+                &mut output,
+                &[
+                    &thread.id,
+                    &reply.id,
+                    &thread.id,
+                    &reply.author,
+                    &reply.created_at.to_rfc3339(),
+                    &resolved_at,
+                    node_id,
+                    node_name,
+                    &path,
+                    &reply.message,
+                ],
+            );
+        }
+    }
+
     output
 }
 
+fn comment_location(thread: &CommentThread) -> Option<String> {
+    let anchor = thread.anchor.as_ref()?;
+    Some(match (&anchor.name, &anchor.path) {
+        (Some(name), Some(path)) => {
+            format!("{} > {name} (`{}`)", path.to_path_string(), anchor.node_id)
+        }
+        _ => format!("deleted node (`{}`)", anchor.node_id),
+    })
+}
+
+fn push_csv_row(output: &mut String, fields: &[&str]) {
+    let row: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                (*field).to_string()
+            }
+        })
+        .collect();
+    output.push('\n');
+    output.push_str(&row.join(","));
+}
+
+/// Left-aligned columns separated by two spaces; the last column is not padded
+fn format_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header_row = headers.iter().map(|h| (*h).to_string()).collect();
+    let mut output = String::new();
+    for row in std::iter::once(header_row).chain(rows) {
+        let last = row.len().saturating_sub(1);
+        for (i, cell) in row.iter().enumerate() {
+            if i == last {
+                output.push_str(cell);
+            } else {
+                let padding = widths[i] - cell.chars().count();
+                let _ = write!(output, "{cell}{}  ", " ".repeat(padding));
+            }
+        }
+        output.truncate(output.trim_end_matches(' ').len());
+        output.push('\n');
+    }
+    output.pop();
+
+    output
+}
+
+fn push_lines(output: &mut String, sign: char, text: &str) {
+    for line in text.lines() {
+        let _ = writeln!(output, "{sign}{line}");
+    }
+}
+
+fn format_paints(paints: &[Paint]) -> String {
+    if paints.is_empty() {
+        return "none".to_string();
+    }
+
+    paints
+        .iter()
+        .map(|paint| {
+            let mut label = paint.paint_type.clone();
+            if let Some(color) = &paint.color {
+                let _ = write!(label, " {}", hex_color(color));
+            }
+            if !paint.gradient_stops.is_empty() {
+                let stops: Vec<String> = paint
+                    .gradient_stops
+                    .iter()
+                    .map(|stop| hex_color(&stop.color))
+                    .collect();
+                let _ = write!(label, " {}", stops.join("→"));
+            }
+            if (paint.opacity - 1.0).abs() > f64::EPSILON {
+                let _ = write!(label, " {:.0}%", paint.opacity * 100.0);
+            }
+            if !paint.visible {
+                label.push_str(" (hidden)");
+            }
+            label
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn hex_color(color: &Color) -> String {
+    let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let mut hex = format!(
+        "#{:02X}{:02X}{:02X}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    );
+    if color.a < 1.0 {
+        let _ = write!(hex, "{:02X}", channel(color.a));
+    }
+    hex
+}
+
+/// Only the style properties that differ, as `key=value` lists for each side
+fn style_delta(from: Option<&TypeStyle>, to: Option<&TypeStyle>) -> (String, String) {
+    let fields = |style: Option<&TypeStyle>| match serde_json::to_value(style) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let (old, new) = (fields(from), fields(to));
+
+    let mut keys: Vec<&String> = old.keys().collect();
+    keys.extend(new.keys().filter(|k| !old.contains_key(*k)));
+
+    let show = |value: Option<&serde_json::Value>| match value {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+        None => "unset".to_string(),
+    };
+
+    let (mut before, mut after) = (Vec::new(), Vec::new());
+    for key in keys {
+        let (a, b) = (old.get(key), new.get(key));
+        if a != b {
+            before.push(format!("{key}={}", show(a)));
+            after.push(format!("{key}={}", show(b)));
+        }
+    }
+
+    (before.join(" "), after.join(" "))
+}
+
+pub(crate) fn format_dimensions(dimensions: Option<Dimensions>) -> String {
+    dimensions.map_or_else(|| "-".to_string(), |d| format!("{}x{}", d.width, d.height))
+}
+
+fn escape_table_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,13 +747,20 @@ mod tests {
                 text: "Hello, World!".to_string(),
                 path: HierarchyPath::new("Page 1".to_string(), vec!["Frame 1".to_string()]),
                 sequence_number: 0,
-                style: Some(TextStyleInfo {
-                    font_family: "Inter".to_string(),
-                    font_size: 16.0,
-                    font_weight: 400,
-                }),
+                style: Some(TextStyleInfo::new("Inter".to_string(), 16.0, 400)),
+                runs: vec![],
             }],
-            elements: None,
+            elements: Some(vec![DesignElement {
+                id: "1:0".to_string(),
+                name: "Checkout | Step 1".to_string(),
+                element_type: "FRAME".to_string(),
+                path: HierarchyPath::new("Page 1".to_string(), vec![]),
+                dimensions: Some(Dimensions {
+                    width: 375.0,
+                    height: 812.0,
+                }),
+                child_count: 1,
+            }]),
             images: None,
             stats: ExtractionStats {
                 total_pages: 1,
@@ -191,6 +780,7 @@ mod tests {
         let json = format_json(&result, false).unwrap();
         assert!(json.contains("Test File"));
         assert!(json.contains("Hello, World!"));
+        assert!(json.contains("\"elementType\":\"FRAME\""));
     }
 
     #[test]
@@ -200,6 +790,8 @@ mod tests {
         assert!(text.contains("Test File"));
         assert!(text.contains("Hello, World!"));
         assert!(text.contains("Statistics:"));
+        assert!(text.contains("FRAME Checkout | Step 1"));
+        assert!(text.contains("Size: 375x812"));
     }
 
     #[test]
@@ -209,5 +801,161 @@ mod tests {
         assert!(md.contains("# Test File"));
         assert!(md.contains("Hello, World!"));
         assert!(md.contains("## Statistics"));
+        assert!(md.contains("## Design Elements"));
+        assert!(md.contains("| FRAME | Checkout \\| Step 1 | Page 1 | 375x812 | 1 |"));
+    }
+
+    fn create_test_diff() -> FileDiff {
+        use crate::models::diff::{DiffSide, DiffSummary};
+
+        let side = |version: &str| DiffSide {
+            version: version.to_string(),
+            last_modified: Utc::now(),
+        };
+        let path = HierarchyPath::new("Mobile".to_string(), vec!["Cart".to_string()]);
+        let node = |id: &str, node_type: &str, name: &str, status| NodeDiff {
+            node_id: id.to_string(),
+            previous_id: None,
+            node_type: node_type.to_string(),
+            name: name.to_string(),
+            path: path.clone(),
+            status,
+            descendants: 0,
+            characters: None,
+            changes: vec![],
+        };
+
+        let mut title = node("1:2", "TEXT", "Title", NodeStatus::Changed);
+        title.changes = vec![
+            Change::Characters {
+                from: "Your basket".to_string(),
+                to: "Your cart".to_string(),
+            },
+            Change::Style {
+                from: Some(Box::new(TypeStyle {
+                    font_family: Some("Inter".to_string()),
+                    font_weight: Some(500),
+                    ..TypeStyle::default()
+                })),
+                to: Some(Box::new(TypeStyle {
+                    font_family: Some("Inter".to_string()),
+                    font_weight: Some(700),
+                    ..TypeStyle::default()
+                })),
+            },
+        ];
+        let mut promo = node("1:9", "FRAME", "Promo", NodeStatus::Added);
+        promo.descendants = 2;
+
+        FileDiff {
+            file_key: "test123".to_string(),
+            file_name: "Test File".to_string(),
+            from: side("1001"),
+            to: side("1002"),
+            summary: DiffSummary {
+                added: 1,
+                characters: 1,
+                style: 1,
+                ..DiffSummary::default()
+            },
+            nodes: vec![title, promo],
+        }
+    }
+
+    #[test]
+    fn test_format_diff_text() {
+        let text = format_diff_text(&create_test_diff());
+        assert!(text.starts_with("--- Test File @ 1001\n+++ Test File @ 1002\n"));
+        assert!(text.contains("@@ Mobile > Cart > Title (1:2) @@\n-Your basket\n+Your cart\n"));
+        assert!(text.contains("-style: fontWeight=500\n+style: fontWeight=700\n"));
+        assert!(text.contains("+FRAME added (2 nested nodes)"));
+    }
+
+    #[test]
+    fn test_format_diff_markdown() {
+        let md = format_diff_markdown(&create_test_diff());
+        assert!(md.contains("| 1 | 0 | 0 | 0 | 1 | 0 | 0 | 1 |"));
+        assert!(
+            md.contains(
+                "## Added\n\n- **FRAME** Mobile > Cart > Promo (`1:9`) with 2 nested nodes"
+            )
+        );
+        assert!(md.contains("  - Text: \"Your basket\" → \"Your cart\""));
+        assert!(!md.contains("## Removed"));
+    }
+
+    #[test]
+    fn test_format_paints() {
+        let paint: Paint = serde_json::from_value(serde_json::json!({
+            "type": "SOLID",
+            "opacity": 0.5,
+            "color": { "r": 1.0, "g": 0.5, "b": 0.0, "a": 1.0 }
+        }))
+        .unwrap();
+        assert_eq!(format_paints(&[paint]), "SOLID #FF8000 50%");
+        assert_eq!(format_paints(&[]), "none");
+    }
+
+    fn create_test_comments() -> CommentReport {
+        use crate::models::comment::{CommentAnchor, CommentReply};
+
+        let created_at = "2025-01-10T09:00:00Z".parse().unwrap();
+        CommentReport {
+            file_key: "test123".to_string(),
+            file_name: "Test File".to_string(),
+            threads: vec![CommentThread {
+                id: "1".to_string(),
+                number: Some("3".to_string()),
+                author: "ana".to_string(),
+                message: "Tighten this, \"please\"".to_string(),
+                created_at,
+                resolved_at: None,
+                anchor: Some(CommentAnchor {
+                    node_id: "1:2".to_string(),
+                    name: Some("Title".to_string()),
+                    path: Some(HierarchyPath {
+                        page_name: "Mobile".to_string(),
+                        section_name: None,
+                        frame_names: vec!["Cart".to_string()],
+                        group_names: None,
+                    }),
+                }),
+                replies: vec![CommentReply {
+                    id: "2".to_string(),
+                    author: "ben".to_string(),
+                    message: "Done".to_string(),
+                    created_at,
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_format_comments_markdown() {
+        let md = format_comments_markdown(&create_test_comments());
+        assert!(md.contains("## #3 ana · 2025-01-10 09:00 UTC\n"));
+        assert!(md.contains("**On:** Mobile > Cart > Title (`1:2`)"));
+        assert!(md.contains("- **ben** (2025-01-10 09:00 UTC): Done"));
+    }
+
+    #[test]
+    fn test_format_comments_csv() {
+        let csv = format_comments_csv(&create_test_comments());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(",1:2,Title,Mobile > Cart,\"Tighten this, \"\"please\"\"\""));
+        assert!(lines[2].starts_with("1,2,1,ben,"));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let table = format_table(
+            &["ID", "NAME"],
+            vec![
+                vec!["1".to_string(), "Design System".to_string()],
+                vec!["12345".to_string(), "Checkout".to_string()],
+            ],
+        );
+        assert_eq!(table, "ID     NAME\n1      Design System\n12345  Checkout");
     }
 }
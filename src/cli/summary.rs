@@ -3,7 +3,8 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fmt::Write;
 
-use crate::models::extraction::{ExtractionResult, TextNodeType};
+use crate::cli::output::format_dimensions;
+use crate::models::extraction::{ExtractionResult, HierarchyPath, TextNodeType};
 
 const MIN_DESCRIPTION_LENGTH: usize = 50;
 
@@ -12,6 +13,7 @@ struct SectionContent {
     notes: Vec<String>,
     descriptions: Vec<String>,
     labels: Vec<String>,
+    elements: Vec<String>,
 }
 
 pub fn format_summary(result: &ExtractionResult) -> String {
@@ -24,18 +26,7 @@ pub fn format_summary(result: &ExtractionResult) -> String {
             continue;
         }
 
-        let page = &text.path.page_name;
-        let section = text
-            .path
-            .section_name
-            .clone()
-            .unwrap_or_else(|| "General".to_string());
-
-        let content = pages
-            .entry(page.clone())
-            .or_default()
-            .entry(section)
-            .or_default();
+        let content = section_content(&mut pages, &text.path);
 
         let trimmed = text.text.trim().to_string();
         match text.node_type {
@@ -50,9 +41,36 @@ pub fn format_summary(result: &ExtractionResult) -> String {
         }
     }
 
+    for element in result.elements.iter().flatten() {
+        section_content(&mut pages, &element.path)
+            .elements
+            .push(format!(
+                "{} {} ({})",
+                element.element_type,
+                element.name,
+                format_dimensions(element.dimensions)
+            ));
+    }
+
     build_output(&result.metadata.file_name, &pages)
 }
 
+fn section_content<'a>(
+    pages: &'a mut BTreeMap<String, BTreeMap<String, SectionContent>>,
+    path: &HierarchyPath,
+) -> &'a mut SectionContent {
+    let section = path
+        .section_name
+        .clone()
+        .unwrap_or_else(|| "General".to_string());
+
+    pages
+        .entry(path.page_name.clone())
+        .or_default()
+        .entry(section)
+        .or_default()
+}
+
 fn is_description(text: &str) -> bool {
     text.len() > MIN_DESCRIPTION_LENGTH || text.contains('\n')
 }
@@ -93,6 +111,14 @@ fn build_output(
                 out.push('\n');
             }
 
+            if !content.elements.is_empty() {
+                let _ = writeln!(out, "#### Elements\n");
+                for element in &content.elements {
+                    let _ = writeln!(out, "- {element}");
+                }
+                out.push('\n');
+            }
+
             let _ = writeln!(out, "---\n");
         }
     }
@@ -105,7 +131,7 @@ mod tests {
     use super::*;
     use crate::models::document::EditorType;
     use crate::models::extraction::{
-        DocumentStructure, ExtractedText, ExtractionStats, FileMetadata, HierarchyPath,
+        DesignElement, Dimensions, DocumentStructure, ExtractedText, ExtractionStats, FileMetadata,
     };
     use chrono::Utc;
 
@@ -150,6 +176,7 @@ mod tests {
                 },
                 sequence_number: 0,
                 style: None,
+                runs: vec![],
             },
             ExtractedText {
                 node_id: "2".to_string(),
@@ -163,6 +190,7 @@ mod tests {
                 },
                 sequence_number: 1,
                 style: None,
+                runs: vec![],
             },
         ];
 
@@ -188,6 +216,7 @@ mod tests {
                 path: HierarchyPath::new("Page".to_string(), vec![]),
                 sequence_number: 0,
                 style: None,
+                runs: vec![],
             },
             ExtractedText {
                 node_id: "2".to_string(),
@@ -196,6 +225,7 @@ mod tests {
                 path: HierarchyPath::new("Page".to_string(), vec![]),
                 sequence_number: 1,
                 style: None,
+                runs: vec![],
             },
         ];
 
@@ -204,4 +234,26 @@ mod tests {
 
         assert_eq!(output.matches("Duplicate").count(), 1);
     }
+
+    #[test]
+    fn test_elements_section() {
+        let mut result = create_test_result(vec![]);
+        result.elements = Some(vec![DesignElement {
+            id: "1:1".to_string(),
+            name: "Cart".to_string(),
+            element_type: "FRAME".to_string(),
+            path: HierarchyPath::new("Mobile".to_string(), vec![]),
+            dimensions: Some(Dimensions {
+                width: 375.0,
+                height: 812.0,
+            }),
+            child_count: 2,
+        }]);
+
+        let output = format_summary(&result);
+
+        assert!(output.contains("## Mobile"));
+        assert!(output.contains("#### Elements"));
+        assert!(output.contains("- FRAME Cart (375x812)"));
+    }
 }
@@ -0,0 +1,320 @@
+//! Local receiver for Figma webhooks
+
+use crate::cli::args::WebhookEventType;
+use crate::cli::batch::{BatchDefaults, BatchEntry, extract_entry};
+use crate::cli::commands::run_hook;
+use crate::core::Cache;
+use crate::models::webhook::WebhookPayload;
+use crate::service::Orchestrator;
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use chrono::Utc;
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use parking_lot::Mutex;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// What to run when a file changes
+pub struct Pipeline {
+    pub orchestrator: Orchestrator,
+    pub cache: Arc<Cache>,
+    /// Manifest entries; without a manifest every reported file is extracted with the defaults
+    pub entries: Option<Vec<BatchEntry>>,
+    pub defaults: BatchDefaults,
+    pub output_dir: PathBuf,
+    pub exec: Option<String>,
+}
+
+pub struct Receiver {
+    passcode: String,
+    events: Vec<WebhookEventType>,
+    debounce: Duration,
+    pipeline: Pipeline,
+    log: EventLog,
+    /// Ticket of the latest event per file; a debounced run only proceeds if it still holds it
+    pending: Mutex<HashMap<String, u64>>,
+    tickets: AtomicU64,
+    /// Extractions run one at a time
+    running: tokio::sync::Mutex<()>,
+}
+
+impl Receiver {
+    pub fn new(
+        passcode: String,
+        events: Vec<WebhookEventType>,
+        debounce: Duration,
+        pipeline: Pipeline,
+        event_log: &Path,
+    ) -> Result<Self> {
+        Ok(Self {
+            passcode,
+            events,
+            debounce,
+            pipeline,
+            log: EventLog::open(event_log)?,
+            pending: Mutex::new(HashMap::new()),
+            tickets: AtomicU64::new(0),
+            running: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Accept connections until Ctrl-C
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> Result<()> {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::warn!("Failed to accept connection: {}", e);
+                        continue;
+                    }
+                },
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            };
+
+            let receiver = Arc::clone(&self);
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let receiver = Arc::clone(&receiver);
+                    async move { Ok::<_, Infallible>(receiver.handle(request).await) }
+                });
+
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    tracing::debug!("Connection error: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle(self: Arc<Self>, request: Request<Incoming>) -> Response<Full<Bytes>> {
+        // Health check
+        if request.method() == Method::GET {
+            return respond(StatusCode::OK, "ok");
+        }
+        if request.method() != Method::POST {
+            return respond(StatusCode::METHOD_NOT_ALLOWED, "method not allowed");
+        }
+
+        let Ok(body) = Limited::new(request.into_body(), MAX_BODY_BYTES)
+            .collect()
+            .await
+            .map(|collected| collected.to_bytes())
+        else {
+            return respond(StatusCode::PAYLOAD_TOO_LARGE, "body too large");
+        };
+
+        let Ok(raw) = serde_json::from_slice::<Value>(&body) else {
+            return respond(StatusCode::BAD_REQUEST, "invalid JSON");
+        };
+
+        if !raw["passcode"]
+            .as_str()
+            .is_some_and(|given| passcode_matches(given, &self.passcode))
+        {
+            self.log.append(&json!({
+                "receivedAt": Utc::now(),
+                "status": "rejected",
+                "eventType": raw["event_type"],
+            }));
+            return respond(StatusCode::FORBIDDEN, "invalid passcode");
+        }
+
+        // Event types this build doesn't know are acknowledged so Figma stops retrying
+        let payload = match serde_json::from_value::<WebhookPayload>(raw.clone()) {
+            Ok(payload) => payload,
+            Err(e) => {
+                self.log.append(&json!({
+                    "receivedAt": Utc::now(),
+                    "status": "ignored",
+                    "eventType": raw["event_type"],
+                    "error": e.to_string(),
+                }));
+                return respond(StatusCode::OK, "ok");
+            }
+        };
+
+        let event_type = payload.event.event_type();
+        let trigger = self
+            .events
+            .iter()
+            .find(|event| event.as_str() == event_type)
+            .zip(payload.event.file_key());
+
+        self.log.append(&json!({
+            "receivedAt": Utc::now(),
+            "status": if trigger.is_some() { "accepted" } else { "ignored" },
+            "event": payload,
+        }));
+
+        if let Some((event, file_key)) = trigger {
+            self.schedule(file_key.to_string(), *event);
+        }
+
+        respond(StatusCode::OK, "ok")
+    }
+
+    /// Run the pipeline once no newer event for the file arrived within the debounce window
+    fn schedule(self: &Arc<Self>, file_key: String, event: WebhookEventType) {
+        let ticket = self.tickets.fetch_add(1, Ordering::Relaxed);
+        self.pending.lock().insert(file_key.clone(), ticket);
+
+        let receiver = Arc::clone(self);
+        tokio::spawn(async move {
+            tokio::time::sleep(receiver.debounce).await;
+
+            {
+                let mut pending = receiver.pending.lock();
+                if pending.get(&file_key) != Some(&ticket) {
+                    return;
+                }
+                pending.remove(&file_key);
+            }
+
+            let _running = receiver.running.lock().await;
+            receiver.run(&file_key, event).await;
+        });
+    }
+
+    async fn run(&self, file_key: &str, event: WebhookEventType) {
+        let pipeline = &self.pipeline;
+        let entries: Vec<BatchEntry> = match &pipeline.entries {
+            Some(entries) => entries
+                .iter()
+                .filter(|entry| entry.file_key().is_ok_and(|key| key == file_key))
+                .cloned()
+                .collect(),
+            None => vec![BatchEntry::new(file_key.to_string())],
+        };
+
+        if entries.is_empty() {
+            tracing::info!("{} is not in the manifest, skipping", file_key);
+            return;
+        }
+
+        // The event means any cached copy is out of date
+        if let Err(e) = pipeline.cache.evict_latest(file_key, None) {
+            tracing::warn!("Failed to evict stale cache entries: {}", e);
+        }
+
+        for entry in &entries {
+            match extract_entry(
+                &pipeline.orchestrator,
+                entry,
+                pipeline.defaults,
+                &pipeline.output_dir,
+            )
+            .await
+            {
+                Ok(path) => {
+                    let output = path.display().to_string();
+                    println!("✓ {} {file_key} → {output}", event.as_str());
+                    self.log.append(&json!({
+                        "ranAt": Utc::now(),
+                        "status": "extracted",
+                        "eventType": event.as_str(),
+                        "fileKey": file_key,
+                        "output": output,
+                    }));
+
+                    if let Some(command) = &pipeline.exec {
+                        run_hook(
+                            command,
+                            &[
+                                ("FIGMA_FILE_KEY", file_key),
+                                ("FIGMA_EVENT", event.as_str()),
+                                ("FIGMA_OUTPUT", &output),
+                            ],
+                        )
+                        .await;
+                    }
+                }
+                Err(e) => {
+                    eprintln!("✗ {} {file_key}: {e:#}", event.as_str());
+                    self.log.append(&json!({
+                        "ranAt": Utc::now(),
+                        "status": "failed",
+                        "eventType": event.as_str(),
+                        "fileKey": file_key,
+                        "error": format!("{e:#}"),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// Append-only JSON Lines file
+struct EventLog {
+    file: Mutex<std::fs::File>,
+}
+
+impl EventLog {
+    fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open event log: {}", path.display()))?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    fn append(&self, record: &Value) {
+        if let Err(e) = writeln!(self.file.lock(), "{record}") {
+            tracing::warn!("Failed to write event log: {}", e);
+        }
+    }
+}
+
+/// Plain-text response
+pub(super) fn respond(status: StatusCode, message: &'static str) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from_static(message.as_bytes())));
+    *response.status_mut() = status;
+    response
+}
+
+/// Compare fixed-length digests without short-circuiting, so timing reveals neither the first
+/// differing byte nor the expected length
+fn passcode_matches(given: &str, expected: &str) -> bool {
+    let given = Sha256::digest(given.as_bytes());
+    let expected = Sha256::digest(expected.as_bytes());
+    given
+        .iter()
+        .zip(expected.iter())
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passcode_matches() {
+        assert!(passcode_matches("secret", "secret"));
+        assert!(!passcode_matches("secreT", "secret"));
+        assert!(!passcode_matches("secret!", "secret"));
+        assert!(!passcode_matches("", "secret"));
+    }
+}
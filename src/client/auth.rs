@@ -1,62 +1,585 @@
-use crate::core::config;
+use crate::core::config::{Config, SecureString};
+use crate::core::constants::{DEFAULT_PROFILE, FIGMA_OAUTH_URL, FIGMA_TOKEN_HEADER};
 use crate::core::errors::{Error, Result};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderValue;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-pub struct TokenManager;
+/// Tokens are refreshed this long before they expire
+const REFRESH_MARGIN_SECS: i64 = 60;
 
-impl TokenManager {
-    pub fn store(token: &str) -> Result<()> {
-        validate_token(token)?;
+/// How requests authenticate
+#[derive(Debug, Clone)]
+pub enum Credential {
+    /// Personal access token, sent as `X-Figma-Token`
+    Personal(SecureString),
+    /// OAuth access token, sent as `Authorization: Bearer`
+    OAuth(OAuthToken),
+}
+
+impl Credential {
+    /// Header name and value for a request; the value is marked sensitive so it is never logged
+    pub fn header(&self) -> Result<(&'static str, HeaderValue)> {
+        let (name, value) = match self {
+            Self::Personal(token) => (FIGMA_TOKEN_HEADER, HeaderValue::from_str(token.as_str())),
+            Self::OAuth(token) => {
+                let bearer = SecureString::new(format!("Bearer {}", token.access_token.as_str()));
+                ("Authorization", HeaderValue::from_str(bearer.as_str()))
+            }
+        };
+
+        let mut value = value.map_err(|_| {
+            Error::validation("token", "contains characters not allowed in a header")
+        })?;
+        value.set_sensitive(true);
+        Ok((name, value))
+    }
+
+    pub const fn oauth(&self) -> Option<&OAuthToken> {
+        match self {
+            Self::Personal(_) => None,
+            Self::OAuth(token) => Some(token),
+        }
+    }
+
+    /// Replace every secret this credential holds with `[REDACTED]`
+    pub fn redact(&self, text: &str) -> String {
+        match self {
+            Self::Personal(token) => redact(text, &[token.as_str()]),
+            Self::OAuth(token) => redact(
+                text,
+                &[
+                    token.access_token.as_str(),
+                    token.refresh_token.as_str(),
+                    token.client_secret.as_str(),
+                ],
+            ),
+        }
+    }
+}
+
+impl From<String> for Credential {
+    fn from(token: String) -> Self {
+        Self::Personal(SecureString::new(token))
+    }
+}
+
+/// OAuth tokens plus the app credentials needed to refresh them
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OAuthToken {
+    pub access_token: SecureString,
+    pub refresh_token: SecureString,
+    pub expires_at: DateTime<Utc>,
+    pub client_id: String,
+    pub client_secret: SecureString,
+}
+
+impl OAuthToken {
+    /// Expired, or close enough that it should be refreshed first
+    pub fn is_expired(&self) -> bool {
+        self.expires_at - chrono::Duration::seconds(REFRESH_MARGIN_SECS) <= Utc::now()
+    }
+}
+
+/// Replace each non-empty secret in `text` with `[REDACTED]`
+pub fn redact(text: &str, secrets: &[&str]) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |text, secret| {
+            text.replace(secret, "[REDACTED]")
+        })
+}
+
+/// Token endpoint response; refreshes may omit the refresh token
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: SecureString,
+    #[serde(default)]
+    refresh_token: Option<SecureString>,
+    expires_in: i64,
+}
+
+/// PKCE verifier and its S256 challenge
+pub struct Pkce {
+    pub verifier: String,
+    pub challenge: String,
+}
+
+impl Pkce {
+    pub fn generate() -> Result<Self> {
+        Ok(Self::from_verifier(random_token()?))
+    }
+
+    pub fn from_verifier(verifier: String) -> Self {
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+        Self {
+            verifier,
+            challenge,
+        }
+    }
+}
+
+/// 32 random bytes, base64url encoded
+pub fn random_token() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|e| Error::other(format!("No randomness: {e}")))?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Figma page where the user grants access
+pub fn authorization_url(
+    client_id: &str,
+    redirect_uri: &str,
+    scopes: &[String],
+    state: &str,
+    pkce: &Pkce,
+) -> String {
+    url::Url::parse_with_params(
+        FIGMA_OAUTH_URL,
+        &[
+            ("client_id", client_id),
+            ("redirect_uri", redirect_uri),
+            ("scope", &scopes.join(",")),
+            ("state", state),
+            ("response_type", "code"),
+            ("code_challenge", &pkce.challenge),
+            ("code_challenge_method", "S256"),
+        ],
+    )
+    .map(String::from)
+    .unwrap_or_default()
+}
+
+/// Trade an authorization code for tokens
+pub async fn exchange_code(
+    client: &reqwest::Client,
+    base_url: &str,
+    client_id: &str,
+    client_secret: &str,
+    code: &str,
+    redirect_uri: &str,
+    verifier: &str,
+) -> Result<OAuthToken> {
+    let response = token_request(
+        client,
+        &format!("{base_url}/v1/oauth/token"),
+        client_id,
+        client_secret,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("code_verifier", verifier),
+        ],
+    )
+    .await?;
+
+    Ok(OAuthToken {
+        access_token: response.access_token,
+        refresh_token: response
+            .refresh_token
+            .ok_or_else(|| Error::auth("Token response has no refresh token"))?,
+        expires_at: Utc::now() + chrono::Duration::seconds(response.expires_in),
+        client_id: client_id.to_string(),
+        client_secret: SecureString::new(client_secret.to_string()),
+    })
+}
+
+/// Get a new access token, keeping the refresh token unless a new one is issued
+pub async fn refresh_token(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &OAuthToken,
+) -> Result<OAuthToken> {
+    tracing::info!("Refreshing OAuth access token");
 
-        let path = config::Config::config_path()
-            .ok_or_else(|| Error::io("cannot determine config path"))?;
+    let response = token_request(
+        client,
+        &format!("{base_url}/v1/oauth/refresh"),
+        &token.client_id,
+        token.client_secret.as_str(),
+        &[("refresh_token", token.refresh_token.as_str())],
+    )
+    .await?;
 
+    Ok(OAuthToken {
+        access_token: response.access_token,
+        refresh_token: response
+            .refresh_token
+            .unwrap_or_else(|| token.refresh_token.clone()),
+        expires_at: Utc::now() + chrono::Duration::seconds(response.expires_in),
+        client_id: token.client_id.clone(),
+        client_secret: token.client_secret.clone(),
+    })
+}
+
+async fn token_request(
+    client: &reqwest::Client,
+    url: &str,
+    client_id: &str,
+    client_secret: &str,
+    form: &[(&str, &str)],
+) -> Result<TokenResponse> {
+    let response = client
+        .post(url)
+        .basic_auth(client_id, Some(client_secret))
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| Error::network(format!("Request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let mut secrets: Vec<&str> = form.iter().map(|(_, value)| *value).collect();
+        secrets.push(client_secret);
+        return Err(Error::auth(format!(
+            "Token request failed ({status}): {}",
+            redact(&body, &secrets)
+        )));
+    }
+
+    response
+        .json::<TokenResponse>()
+        .await
+        .map_err(|e| Error::parse(format!("Failed to parse token response: {e}")))
+}
+
+/// Credentials file contents
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CredentialsFile {
+    /// Profile used when none is selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    active: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, StoredProfile>,
+}
+
+impl CredentialsFile {
+    /// Read the file, refusing one that other users can access
+    fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        check_private(path)?;
+        let content = SecureString::new(std::fs::read_to_string(path)?);
+        let mut file: Self = toml::from_str(content.as_str())?;
+
+        // A file without profiles holds a single login at the top level
+        let single: StoredProfile = toml::from_str(content.as_str())?;
+        if single.token.is_some() || single.oauth.is_some() {
+            file.profiles
+                .entry(DEFAULT_PROFILE.to_string())
+                .or_insert(single);
+        }
+
+        Ok(Some(file))
+    }
+
+    /// Write the file, readable and writable by the owner only
+    fn write(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        let mut config = if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
-            toml::from_str(&content)?
-        } else {
-            config::Config::default()
-        };
+        let content = SecureString::new(toml::to_string_pretty(self)?);
+        write_private(path, content.as_str().as_bytes())
+    }
+}
+
+/// One profile's login and settings
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+struct StoredProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<SecureString>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oauth: Option<OAuthToken>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    team: Option<String>,
+}
 
-        config.token = Some(token.to_string());
-        let content = toml::to_string_pretty(&config)?;
-        std::fs::write(&path, content)?;
+impl StoredProfile {
+    fn into_profile(self, name: String) -> Option<Profile> {
+        let credential = self
+            .token
+            .map(Credential::Personal)
+            .or_else(|| self.oauth.map(Credential::OAuth))?;
+
+        Some(Profile {
+            name,
+            credential,
+            team: self.team,
+        })
+    }
+}
+
+#[cfg(unix)]
+fn check_private(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path)?.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(Error::validation(
+            "credentials",
+            format!(
+                "{} is accessible by other users (mode {mode:o}); run 'chmod 600 {}'",
+                path.display(),
+                path.display()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+const fn check_private(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn write_private(path: &Path, content: &[u8]) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        options.mode(0o600);
+        // An existing file keeps its mode when opened, so tighten it before writing
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    options.open(path)?.write_all(content)?;
+    Ok(())
+}
 
+/// A named login, with its own default team and cache
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub name: String,
+    pub credential: Credential,
+    pub team: Option<String>,
+}
+
+/// Profile chosen with `--profile`, overriding the active one
+static SELECTED_PROFILE: OnceLock<String> = OnceLock::new();
+
+pub struct TokenManager;
+
+impl TokenManager {
+    /// Use this profile for the rest of the process instead of the active one
+    pub fn select_profile(name: String) -> Result<()> {
+        validate_profile_name(&name)?;
+        let _ = SELECTED_PROFILE.set(name);
         Ok(())
     }
 
-    pub fn get() -> Result<Option<String>> {
-        if let Ok(token) = std::env::var("FIGMA_TOKEN") {
-            return Ok(Some(token));
+    /// Whether a profile was picked for this process with `--profile` or `FIGMA_PROFILE`
+    pub fn profile_selected() -> bool {
+        SELECTED_PROFILE.get().is_some()
+    }
+
+    /// Selected profile, else the one last chosen with `auth use`, else `default`
+    pub fn profile_name() -> Result<String> {
+        if let Some(name) = SELECTED_PROFILE.get() {
+            return Ok(name.clone());
+        }
+
+        Ok(Self::read()?
+            .active
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string()))
+    }
+
+    /// Store a personal access token, replacing any OAuth login of the profile
+    pub fn store(profile: &str, token: &str) -> Result<()> {
+        validate_token(token)?;
+
+        Self::update(profile, |stored| {
+            stored.token = Some(SecureString::new(token.to_string()));
+            stored.oauth = None;
+        })
+    }
+
+    /// Store OAuth tokens, replacing any personal access token of the profile
+    pub fn store_oauth(profile: &str, token: &OAuthToken) -> Result<()> {
+        Self::update(profile, |stored| {
+            stored.token = None;
+            stored.oauth = Some(token.clone());
+        })
+    }
+
+    /// Set the profile's default team
+    pub fn set_team(profile: &str, team: &str) -> Result<()> {
+        Self::update(profile, |stored| stored.team = Some(team.to_string()))
+    }
+
+    /// Personal access token, if that is how the profile authenticates
+    pub fn get(profile: &str) -> Result<Option<SecureString>> {
+        Ok(
+            Self::credential(profile)?.and_then(|credential| match credential {
+                Credential::Personal(token) => Some(token),
+                Credential::OAuth(_) => None,
+            }),
+        )
+    }
+
+    /// `FIGMA_TOKEN`, then the profile's stored login
+    ///
+    /// A profile picked with `--profile` or `FIGMA_PROFILE` only uses its stored login, so its
+    /// team and cache never get paired with another account's token.
+    pub fn credential(profile: &str) -> Result<Option<Credential>> {
+        if !Self::profile_selected()
+            && let Ok(token) = std::env::var("FIGMA_TOKEN")
+        {
+            return Ok(Some(Credential::from(token)));
         }
 
-        let path = config::Config::config_path();
-        if let Some(p) = path.filter(|p| p.exists()) {
-            let content = std::fs::read_to_string(&p)?;
-            let config: config::Config = toml::from_str(&content)?;
-            return Ok(config.token);
+        Ok(Self::load(profile)?.map(|p| p.credential))
+    }
+
+    /// A stored profile
+    pub fn load(profile: &str) -> Result<Option<Profile>> {
+        Ok(Self::read()?
+            .profiles
+            .remove(profile)
+            .and_then(|stored| stored.into_profile(profile.to_string())))
+    }
+
+    /// Every stored profile, by name
+    pub fn list() -> Result<Vec<Profile>> {
+        Ok(Self::read()?
+            .profiles
+            .into_iter()
+            .filter_map(|(name, stored)| stored.into_profile(name))
+            .collect())
+    }
+
+    /// Make a stored profile the one used by default
+    pub fn activate(profile: &str) -> Result<()> {
+        let path = Self::path()?;
+        let mut file = Self::read()?;
+        if !file.profiles.contains_key(profile) {
+            return Err(Error::not_found(format!(
+                "Profile '{profile}' not found. Run 'figma-cli auth login --profile {profile}' first"
+            )));
         }
 
-        Ok(None)
+        file.active = Some(profile.to_string());
+        file.write(&path)
     }
 
-    pub fn delete() -> Result<()> {
-        let path = config::Config::config_path()
-            .ok_or_else(|| Error::io("cannot determine config path"))?;
+    /// Remove a profile's login
+    pub fn delete(profile: &str) -> Result<()> {
+        let path = Self::path()?;
+        let mut file = Self::read()?;
+        file.profiles.remove(profile);
+        if file.active.as_deref() == Some(profile) {
+            file.active = None;
+        }
 
-        if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
-            let mut config: config::Config = toml::from_str(&content)?;
-            config.token = None;
-            let content = toml::to_string_pretty(&config)?;
-            std::fs::write(&path, content)?;
+        if file.profiles.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            return Ok(());
         }
 
-        Ok(())
+        file.write(&path)
+    }
+
+    /// Where credentials are stored
+    pub fn path() -> Result<PathBuf> {
+        Config::credentials_path().ok_or_else(|| Error::io("cannot determine config path"))
+    }
+
+    /// The credentials file, after moving any token an earlier version left in `config.toml`
+    fn read() -> Result<CredentialsFile> {
+        let path = Self::path()?;
+        let mut file = CredentialsFile::read(&path)?.unwrap_or_default();
+
+        if let Some(legacy) = take_legacy_credentials()? {
+            file.profiles
+                .entry(DEFAULT_PROFILE.to_string())
+                .or_insert(legacy);
+            file.write(&path)?;
+            strip_legacy_credentials()?;
+            tracing::warn!("Moved the stored token to {}", path.display());
+        }
+
+        Ok(file)
+    }
+
+    fn update(profile: &str, change: impl FnOnce(&mut StoredProfile)) -> Result<()> {
+        validate_profile_name(profile)?;
+
+        let mut file = Self::read()?;
+        change(file.profiles.entry(profile.to_string()).or_default());
+        file.write(&Self::path()?)
+    }
+}
+
+/// Token and OAuth login that earlier versions wrote in plaintext to `config.toml`
+fn take_legacy_credentials() -> Result<Option<StoredProfile>> {
+    let Some(path) = Config::config_path().filter(|p| p.exists()) else {
+        return Ok(None);
+    };
+
+    let content = SecureString::new(std::fs::read_to_string(&path)?);
+    let mut table: toml::Table = toml::from_str(content.as_str())?;
+    let stored = StoredProfile {
+        token: table
+            .remove("token")
+            .and_then(|token| token.as_str().map(|t| SecureString::new(t.to_string()))),
+        oauth: table
+            .remove("oauth")
+            .map(toml::Value::try_into)
+            .transpose()?,
+        team: None,
+    };
+
+    Ok((stored.token.is_some() || stored.oauth.is_some()).then_some(stored))
+}
+
+fn strip_legacy_credentials() -> Result<()> {
+    let Some(path) = Config::config_path().filter(|p| p.exists()) else {
+        return Ok(());
+    };
+
+    let mut table: toml::Table = toml::from_str(&std::fs::read_to_string(&path)?)?;
+    let token = table.remove("token");
+    let oauth = table.remove("oauth");
+    if token.is_some() || oauth.is_some() {
+        std::fs::write(&path, toml::to_string_pretty(&table)?)?;
     }
+
+    Ok(())
+}
+
+/// Profile names become directory names, so keep them simple
+fn validate_profile_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(Error::validation(
+            "profile",
+            "use only letters, digits, '-' and '_'",
+        ));
+    }
+
+    Ok(())
 }
 
 fn validate_token(token: &str) -> Result<()> {
@@ -74,3 +597,129 @@ fn validate_token(token: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636, appendix B
+        let pkce = Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+        assert_eq!(
+            pkce.challenge,
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+
+        let generated = Pkce::generate().unwrap();
+        assert_eq!(generated.verifier.len(), 43);
+        assert_ne!(generated.verifier, Pkce::generate().unwrap().verifier);
+    }
+
+    fn oauth_token() -> OAuthToken {
+        OAuthToken {
+            access_token: SecureString::new("figu_access".to_string()),
+            refresh_token: SecureString::new("figur_refresh".to_string()),
+            expires_at: Utc::now() + chrono::Duration::seconds(30),
+            client_id: "id".to_string(),
+            client_secret: SecureString::new("app_secret".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_credential_headers() {
+        let personal = Credential::from("figd_token".to_string());
+        let (name, value) = personal.header().unwrap();
+        assert_eq!(name, "X-Figma-Token");
+        assert_eq!(value, "figd_token");
+        assert!(value.is_sensitive());
+
+        let token = oauth_token();
+        assert!(token.is_expired());
+
+        let (name, value) = Credential::OAuth(token).header().unwrap();
+        assert_eq!(name, "Authorization");
+        assert_eq!(value, "Bearer figu_access");
+    }
+
+    #[test]
+    fn test_secrets_are_redacted() {
+        let credential = Credential::OAuth(oauth_token());
+        assert!(!format!("{credential:?}").contains("figu_access"));
+        assert_eq!(
+            credential.redact("bad token figu_access, figur_refresh"),
+            "bad token [REDACTED], [REDACTED]"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_credentials_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("figma-cli/credentials.toml");
+
+        let mut file = CredentialsFile::default();
+        file.profiles.insert(
+            "client-a".to_string(),
+            StoredProfile {
+                oauth: Some(oauth_token()),
+                ..StoredProfile::default()
+            },
+        );
+        file.write(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let mut read = CredentialsFile::read(&path).unwrap().unwrap();
+        let profile = read.profiles.remove("client-a").unwrap();
+        assert_eq!(profile.oauth.unwrap().access_token.as_str(), "figu_access");
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = CredentialsFile::read(&path).unwrap_err();
+        assert!(err.to_string().contains("chmod 600"));
+
+        // Rewriting tightens the mode again
+        file.write(&path).unwrap();
+        assert!(CredentialsFile::read(&path).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_single_login_file_reads_as_default_profile() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("credentials.toml");
+        write_private(&path, b"token = \"figd_single\"\n").unwrap();
+
+        let file = CredentialsFile::read(&path).unwrap().unwrap();
+        let profile = file.profiles[DEFAULT_PROFILE].clone();
+        assert_eq!(profile.token.unwrap().as_str(), "figd_single");
+        assert!(file.active.is_none());
+    }
+
+    #[test]
+    fn test_profile_names() {
+        assert!(validate_profile_name("client-a_2").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../other").is_err());
+    }
+
+    #[test]
+    fn test_authorization_url() {
+        let pkce = Pkce::from_verifier("verifier".to_string());
+        let url = authorization_url(
+            "app",
+            "http://localhost:8976/callback",
+            &["file_content:read".to_string(), "projects:read".to_string()],
+            "xyz",
+            &pkce,
+        );
+        let parsed = url::Url::parse(&url).unwrap();
+        let params: std::collections::HashMap<_, _> = parsed.query_pairs().collect();
+        assert_eq!(params["redirect_uri"], "http://localhost:8976/callback");
+        assert_eq!(params["scope"], "file_content:read,projects:read");
+        assert_eq!(params["code_challenge"], pkce.challenge);
+        assert_eq!(params["code_challenge_method"], "S256");
+    }
+}
@@ -1,20 +1,81 @@
 use crate::core::errors::Error;
+use serde::Deserialize;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-pub fn from_status_code(status: u16, message: String) -> Error {
+/// Error body returned by the Figma API, e.g. `{"status": 404, "err": "Not found"}`
+#[derive(Debug, Deserialize)]
+struct FigmaErrorBody {
+    err: Option<String>,
+    message: Option<String>,
+}
+
+pub fn from_status_code(status: u16, message: String, endpoint: &str) -> Error {
     match status {
         401 => Error::auth(message),
         403 => Error::auth(format!("Access denied: {message}")),
-        404 => Error::not_found(message),
+        404 => match file_key_of(endpoint) {
+            Some(file_key) => Error::file_not_found(file_key),
+            None => Error::not_found(format!("{endpoint}: {message}")),
+        },
         429 => Error::RateLimit,
-        500..=599 => Error::network(format!("Server error ({status}): {message}")),
-        _ => Error::network(format!("HTTP {status}: {message}")),
+        _ => Error::http(status, message, endpoint),
     }
 }
 
-pub fn parse_retry_after(header_value: &str) -> Option<u64> {
-    header_value.parse::<u64>().ok()
+/// Statuses worth retrying: timeouts and transient server failures
+pub const fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 408 | 500 | 502 | 503 | 504)
+}
+
+/// Whether a failed send may succeed when repeated: timeouts, refused connections and
+/// connections dropped mid-request
+pub fn is_retryable_request(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || is_connection_dropped(error)
+}
+
+/// Whether an I/O error in the source chain shows the connection was reset or closed early
+fn is_connection_dropped(error: &reqwest::Error) -> bool {
+    use std::io::ErrorKind;
+
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>()
+            && matches!(
+                io.kind(),
+                ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::UnexpectedEof
+            )
+        {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
+/// Message from a Figma error body, or the body itself when it is not one
+pub fn error_message(body: &str) -> String {
+    serde_json::from_str::<FigmaErrorBody>(body)
+        .ok()
+        .and_then(|parsed| parsed.err.or(parsed.message))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+/// File key of endpoints that address a file as a whole (`/v1/files/:key` and its direct
+/// sub-resources), where a 404 means the file is missing
+fn file_key_of(endpoint: &str) -> Option<&str> {
+    let start = endpoint.find("/v1/files/")? + "/v1/files/".len();
+    let segments: Vec<&str> = endpoint[start..]
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    match segments.as_slice() {
+        [file_key] | [file_key, _] => Some(file_key),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -23,23 +84,48 @@ mod tests {
 
     #[test]
     fn test_from_status_code() {
-        let err = from_status_code(401, "Unauthorized".to_string());
+        let err = from_status_code(401, "Unauthorized".to_string(), "/v1/me");
         assert!(matches!(err, Error::Auth(_)));
 
-        let err = from_status_code(429, "Rate limit exceeded".to_string());
+        let err = from_status_code(429, "Rate limit exceeded".to_string(), "/v1/me");
         assert!(matches!(err, Error::RateLimit));
 
-        let err = from_status_code(404, "Not found".to_string());
-        assert!(matches!(err, Error::NotFound(_)));
+        let err = from_status_code(404, "Not found".to_string(), "/v1/files/KEY/nodes");
+        assert_eq!(err.to_string(), "not found: file KEY");
 
-        let err = from_status_code(500, "Internal server error".to_string());
-        assert!(matches!(err, Error::Network(_)));
+        let err = from_status_code(404, "Not found".to_string(), "/v2/webhooks/42");
+        assert_eq!(err.to_string(), "not found: /v2/webhooks/42: Not found");
+
+        let err = from_status_code(502, "Bad gateway".to_string(), "/v1/files/KEY");
+        assert!(matches!(
+            err,
+            Error::Http { status: 502, ref endpoint, .. } if endpoint == "/v1/files/KEY"
+        ));
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        for status in [408, 500, 502, 503, 504] {
+            assert!(is_retryable_status(status), "{status}");
+        }
+        for status in [400, 401, 403, 404, 429, 501] {
+            assert!(!is_retryable_status(status), "{status}");
+        }
     }
 
     #[test]
-    fn test_parse_retry_after() {
-        assert_eq!(parse_retry_after("60"), Some(60));
-        assert_eq!(parse_retry_after("invalid"), None);
-        assert_eq!(parse_retry_after("3600"), Some(3600));
+    fn test_error_message() {
+        assert_eq!(
+            error_message(r#"{"status": 400, "err": "Invalid parameter"}"#),
+            "Invalid parameter"
+        );
+        assert_eq!(
+            error_message(r#"{"error": true, "status": 403, "message": "Forbidden"}"#),
+            "Forbidden"
+        );
+        assert_eq!(
+            error_message("<html>Bad gateway</html>\n"),
+            "<html>Bad gateway</html>"
+        );
     }
 }
@@ -1,32 +1,46 @@
 //! Figma API HTTP client
 
-use crate::client::error::Result;
-use crate::client::retry::{RetryConfig, retry_with_backoff};
+use crate::client::auth::{Credential, OAuthToken, refresh_token};
+use crate::client::error::{Result, error_message, from_status_code};
+use crate::client::retry::RetryConfig;
+use crate::client::scheduler::Scheduler;
 use crate::core::cache::Cache;
+use crate::core::constants::FIGMA_API_BASE;
 use crate::core::errors::Error;
 use crate::models::document::FigmaFile;
+use parking_lot::RwLock;
 use reqwest::Client as HttpClient;
+use reqwest::header::HeaderValue;
 use std::sync::Arc;
 use std::time::Duration;
 
-const FIGMA_API_BASE: &str = "https://api.figma.com/v1";
+/// Largest page the versions endpoint serves
+const VERSIONS_PAGE_SIZE: u32 = 50;
+
+/// Called with the new tokens whenever an OAuth access token is refreshed
+pub type RefreshHook = Arc<dyn Fn(&OAuthToken) + Send + Sync>;
 
 /// Figma API client
 pub struct FigmaClient {
     client: HttpClient,
-    token: String,
+    credential: RwLock<Credential>,
+    base_url: String,
     retry_config: RetryConfig,
+    scheduler: Arc<Scheduler>,
     cache: Option<Arc<Cache>>,
+    /// Serializes refreshes so concurrent 401s exchange the refresh token once
+    refresh_lock: tokio::sync::Mutex<()>,
+    on_refresh: Option<RefreshHook>,
 }
 
 impl FigmaClient {
-    /// Create a new Figma client with the given access token
-    pub fn new(token: String) -> Result<Self> {
-        Self::with_timeout(token, 30)
+    /// Create a new Figma client with a personal access token or OAuth tokens
+    pub fn new(credential: impl Into<Credential>) -> Result<Self> {
+        Self::with_timeout(credential, 30)
     }
 
     /// Create a new Figma client with custom timeout
-    pub fn with_timeout(token: String, timeout_secs: u64) -> Result<Self> {
+    pub fn with_timeout(credential: impl Into<Credential>, timeout_secs: u64) -> Result<Self> {
         let client = HttpClient::builder()
             .timeout(Duration::from_secs(timeout_secs))
             .build()
@@ -34,9 +48,13 @@ impl FigmaClient {
 
         Ok(Self {
             client,
-            token,
+            credential: RwLock::new(credential.into()),
+            base_url: FIGMA_API_BASE.to_string(),
             retry_config: RetryConfig::default(),
+            scheduler: Scheduler::global(),
             cache: None,
+            refresh_lock: tokio::sync::Mutex::new(()),
+            on_refresh: None,
         })
     }
 
@@ -46,33 +64,120 @@ impl FigmaClient {
         self
     }
 
+    /// Schedule requests with a scheduler other than the process-wide one
+    pub fn with_scheduler(mut self, scheduler: Arc<Scheduler>) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Use a different API base URL (e.g. a local stand-in or egress proxy)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
     /// Enable caching with the given cache instance
     pub fn with_cache(mut self, cache: Arc<Cache>) -> Self {
         self.cache = Some(cache);
         self
     }
 
+    /// Persist refreshed OAuth tokens, e.g. back to the config file
+    pub fn with_refresh_hook(mut self, hook: RefreshHook) -> Self {
+        self.on_refresh = Some(hook);
+        self
+    }
+
     /// Set authentication token
     pub fn set_token(&mut self, token: String) {
-        self.token = token;
+        *self.credential.get_mut() = Credential::from(token);
+    }
+
+    /// Current credential, with any refreshed OAuth tokens
+    pub fn credential(&self) -> Credential {
+        self.credential.read().clone()
+    }
+
+    /// Send an authenticated request, retrying transient failures
+    ///
+    /// With OAuth, an expired access token is refreshed first, and a 401 triggers one refresh and
+    /// a second attempt.
+    async fn send(
+        &self,
+        request: impl Fn(&HttpClient) -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        if self
+            .credential
+            .read()
+            .oauth()
+            .is_some_and(OAuthToken::is_expired)
+        {
+            self.refresh(None).await?;
+        }
+
+        let (response, used) = self.send_with_retry(&request).await?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED
+            || self.credential.read().oauth().is_none()
+        {
+            return Ok(response);
+        }
+
+        self.refresh(Some(&used)).await?;
+        Ok(self.send_with_retry(&request).await?.0)
+    }
+
+    /// Send through the scheduler; also returns the auth header value that was used
+    async fn send_with_retry(
+        &self,
+        request: &impl Fn(&HttpClient) -> reqwest::RequestBuilder,
+    ) -> Result<(reqwest::Response, HeaderValue)> {
+        let (header, value) = self.credential.read().header()?;
+
+        let response = self
+            .scheduler
+            .send(
+                || request(&self.client).header(header, value.clone()),
+                self.retry_config,
+            )
+            .await?;
+
+        Ok((response, value))
+    }
+
+    /// Refresh the OAuth access token
+    ///
+    /// `rejected` is the header value a request was refused with; if another request has replaced
+    /// that token in the meantime, no refresh is needed.
+    async fn refresh(&self, rejected: Option<&HeaderValue>) -> Result<()> {
+        let _refreshing = self.refresh_lock.lock().await;
+
+        let current = self.credential();
+        let Some(token) = current.oauth() else {
+            return Ok(());
+        };
+        let already_refreshed = match rejected {
+            Some(rejected) => current.header()?.1 != rejected,
+            None => !token.is_expired(),
+        };
+        if already_refreshed {
+            return Ok(());
+        }
+
+        let refreshed = refresh_token(&self.client, &self.base_url, token).await?;
+        *self.credential.write() = Credential::OAuth(refreshed.clone());
+
+        if let Some(hook) = &self.on_refresh {
+            hook(&refreshed);
+        }
+
+        Ok(())
     }
 
     /// Validate authentication by making a test request
     pub async fn validate_auth(&self) -> Result<UserInfo> {
-        let url = format!("{FIGMA_API_BASE}/me");
+        let url = format!("{}/v1/me", self.base_url);
 
-        let response = retry_with_backoff(
-            || async {
-                self.client
-                    .get(&url)
-                    .header("X-Figma-Token", &self.token)
-                    .send()
-                    .await
-                    .map_err(|e| Error::network(format!("Request failed: {e}")))
-            },
-            self.retry_config,
-        )
-        .await?;
+        let response = self.send(|client| client.get(&url)).await?;
 
         if response.status().is_success() {
             response
@@ -81,52 +186,57 @@ impl FigmaClient {
                 .map_err(|e| Error::parse(format!("Failed to parse user info: {e}")))
         } else if response.status().as_u16() == 401 {
             Err(Error::Auth("Invalid token".to_string()))
-        } else if response.status().as_u16() == 429 {
-            Err(Error::RateLimit)
         } else {
-            Err(Error::other(format!(
-                "API request failed with status: {}",
-                response.status()
-            )))
+            Err(self.handle_error_response(response).await)
         }
     }
 
-    /// Get a Figma file by key with optional depth parameter
-    pub async fn get_file(&self, file_key: &str, depth: Option<u32>) -> Result<FigmaFile> {
+    /// Get a Figma file by key with optional depth, optionally pinned to a version id
+    pub async fn get_file(
+        &self,
+        file_key: &str,
+        depth: Option<u32>,
+        version: Option<&str>,
+    ) -> Result<FigmaFile> {
         if let Some(cache) = &self.cache
-            && let Ok(Some(cached_value)) = cache.get_file(file_key, depth)
+            && let Ok(Some(cached_value)) = cache.get_file(file_key, depth, version)
         {
-            tracing::info!("Cache hit for file: {} (depth: {:?})", file_key, depth);
+            tracing::info!(
+                "Cache hit for file: {} (depth: {:?}, version: {:?})",
+                file_key,
+                depth,
+                version
+            );
             let mut file: FigmaFile = serde_json::from_value(cached_value)
                 .map_err(|e| Error::parse(format!("Cache deserialization failed: {e}")))?;
             file.file_key = file_key.to_string();
             return Ok(file);
         }
 
-        let url = format!("{FIGMA_API_BASE}/files/{file_key}");
+        let url = format!("{}/v1/files/{file_key}", self.base_url);
 
-        tracing::info!("Fetching file: {} (depth: {:?})", file_key, depth);
+        tracing::info!(
+            "Fetching file: {} (depth: {:?}, version: {:?})",
+            file_key,
+            depth,
+            version
+        );
 
-        let response = retry_with_backoff(
-            || async {
-                let mut request = self
-                    .client
-                    .get(&url)
-                    .query(&[("branch_data", "false")])
-                    .header("X-Figma-Token", &self.token);
+        let response = self
+            .send(|client| {
+                let mut request = client.get(&url).query(&[("branch_data", "false")]);
 
                 if let Some(d) = depth {
                     request = request.query(&[("depth", d.to_string())]);
                 }
 
+                if let Some(v) = version {
+                    request = request.query(&[("version", v)]);
+                }
+
                 request
-                    .send()
-                    .await
-                    .map_err(|e| Error::network(format!("Request failed: {e}")))
-            },
-            self.retry_config,
-        )
-        .await?;
+            })
+            .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_error_response(response).await);
@@ -159,7 +269,9 @@ impl FigmaClient {
         if let Some(cache) = &self.cache {
             match serde_json::to_value(&file) {
                 Ok(value) => {
-                    if let Err(e) = cache.put_file(&file.file_key, &file.version, &value, depth) {
+                    if let Err(e) =
+                        cache.put_file(&file.file_key, &file.version, &value, depth, version)
+                    {
                         tracing::warn!("Failed to cache file: {}", e);
                     }
                 }
@@ -172,15 +284,66 @@ impl FigmaClient {
         Ok(file)
     }
 
+    /// Name, version and last modification of a file, always fetched fresh
+    ///
+    /// Requests only the page list (`depth=1`) and bypasses the cache, so it is cheap to poll.
+    pub async fn get_file_meta(&self, file_key: &str) -> Result<FileMeta> {
+        let url = format!("{}/v1/files/{file_key}", self.base_url);
+
+        tracing::debug!("Polling file: {}", file_key);
+
+        let response = self
+            .send(|client| {
+                client
+                    .get(&url)
+                    .query(&[("depth", "1"), ("branch_data", "false")])
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<FileMeta>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse file response: {e}")))
+    }
+
+    /// Branches of a file; requests only the page list and bypasses the cache
+    pub async fn get_branches(&self, file_key: &str) -> Result<BranchesResponse> {
+        let url = format!("{}/v1/files/{file_key}", self.base_url);
+
+        tracing::info!("Listing branches of file: {}", file_key);
+
+        let response = self
+            .send(|client| {
+                client
+                    .get(&url)
+                    .query(&[("depth", "1"), ("branch_data", "true")])
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<BranchesResponse>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse branches response: {e}")))
+    }
+
     /// Get specific nodes from a file
     pub async fn get_nodes(
         &self,
         file_key: &str,
         node_ids: &[String],
         depth: Option<u32>,
+        version: Option<&str>,
     ) -> Result<NodesResponse> {
         if let Some(cache) = &self.cache
-            && let Ok(Some(cached)) = cache.get_nodes(file_key, node_ids, depth)
+            && let Ok(Some(cached)) = cache.get_nodes(file_key, node_ids, depth, version)
         {
             tracing::info!(
                 "Cache hit for {} nodes from file: {}",
@@ -191,31 +354,26 @@ impl FigmaClient {
                 .map_err(|e| Error::parse(format!("Cache deserialization failed: {e}")));
         }
 
-        let url = format!("{FIGMA_API_BASE}/files/{file_key}/nodes");
+        let url = format!("{}/v1/files/{file_key}/nodes", self.base_url);
         let ids = node_ids.join(",");
 
         tracing::info!("Fetching {} nodes from file: {}", node_ids.len(), file_key);
 
-        let response = retry_with_backoff(
-            || async {
-                let mut request = self
-                    .client
-                    .get(&url)
-                    .header("X-Figma-Token", &self.token)
-                    .query(&[("ids", ids.as_str())]);
+        let response = self
+            .send(|client| {
+                let mut request = client.get(&url).query(&[("ids", ids.as_str())]);
 
                 if let Some(d) = depth {
                     request = request.query(&[("depth", d.to_string())]);
                 }
 
+                if let Some(v) = version {
+                    request = request.query(&[("version", v)]);
+                }
+
                 request
-                    .send()
-                    .await
-                    .map_err(|e| Error::network(format!("Request failed: {e}")))
-            },
-            self.retry_config,
-        )
-        .await?;
+            })
+            .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_error_response(response).await);
@@ -226,10 +384,19 @@ impl FigmaClient {
             .await
             .map_err(|e| Error::parse(format!("Failed to parse nodes response: {e}")))?;
 
+        let missing: Vec<&str> = node_ids
+            .iter()
+            .map(String::as_str)
+            .filter(|id| !matches!(nodes_response.nodes.get(*id), Some(Some(_))))
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::node_not_found(file_key, &missing));
+        }
+
         if let Some(cache) = &self.cache {
             match serde_json::to_value(&nodes_response) {
                 Ok(value) => {
-                    if let Err(e) = cache.put_nodes(file_key, node_ids, depth, &value) {
+                    if let Err(e) = cache.put_nodes(file_key, node_ids, depth, version, &value) {
                         tracing::warn!("Failed to cache nodes: {}", e);
                     }
                 }
@@ -242,6 +409,235 @@ impl FigmaClient {
         Ok(nodes_response)
     }
 
+    /// Get one page of a file's version history, newest first
+    pub async fn get_versions(
+        &self,
+        file_key: &str,
+        page_size: Option<u32>,
+        before: Option<&str>,
+    ) -> Result<VersionsResponse> {
+        let url = format!("{}/v1/files/{file_key}/versions", self.base_url);
+
+        tracing::info!(
+            "Fetching versions for file: {} (before: {:?})",
+            file_key,
+            before
+        );
+
+        let response = self
+            .send(|client| {
+                let mut request = client.get(&url);
+
+                if let Some(size) = page_size {
+                    request = request.query(&[("page_size", size.to_string())]);
+                }
+
+                if let Some(id) = before {
+                    request = request.query(&[("before", id)]);
+                }
+
+                request
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<VersionsResponse>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse versions response: {e}")))
+    }
+
+    /// Walk the version history page by page, stopping after `limit` versions
+    pub async fn list_versions(
+        &self,
+        file_key: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<FileVersion>> {
+        let mut versions: Vec<FileVersion> = Vec::new();
+
+        loop {
+            let remaining = limit.map(|l| l.saturating_sub(versions.len()));
+            if remaining == Some(0) {
+                break;
+            }
+
+            let page_size = remaining.map_or(VERSIONS_PAGE_SIZE, |r| {
+                r.min(VERSIONS_PAGE_SIZE as usize) as u32
+            });
+            let before = versions.last().map(|v| v.id.clone());
+            let page = self
+                .get_versions(file_key, Some(page_size), before.as_deref())
+                .await?;
+
+            let has_more = page.pagination.next_page.is_some() && !page.versions.is_empty();
+            versions.extend(page.versions);
+
+            if !has_more {
+                break;
+            }
+        }
+
+        if let Some(l) = limit {
+            versions.truncate(l);
+        }
+
+        Ok(versions)
+    }
+
+    /// Get all comments on a file, replies included
+    pub async fn get_comments(&self, file_key: &str) -> Result<Vec<Comment>> {
+        let url = comments_url(&self.base_url, file_key, None);
+
+        tracing::info!("Fetching comments for file: {}", file_key);
+
+        let response = self.send(|client| client.get(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<CommentsResponse>()
+            .await
+            .map(|r| r.comments)
+            .map_err(|e| Error::parse(format!("Failed to parse comments response: {e}")))
+    }
+
+    /// Post a comment or, when `comment_id` is set, a reply
+    pub async fn post_comment(&self, file_key: &str, comment: &NewComment) -> Result<Comment> {
+        let url = comments_url(&self.base_url, file_key, None);
+
+        tracing::info!("Posting comment on file: {}", file_key);
+
+        let response = self.send(|client| client.post(&url).json(comment)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<Comment>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse comment response: {e}")))
+    }
+
+    /// Delete a comment; only its author can do this
+    pub async fn delete_comment(&self, file_key: &str, comment_id: &str) -> Result<()> {
+        let url = comments_url(&self.base_url, file_key, Some(comment_id));
+
+        tracing::info!("Deleting comment {} on file: {}", comment_id, file_key);
+
+        let response = self.send(|client| client.delete(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// List the projects of a team
+    pub async fn get_team_projects(&self, team_id: &str) -> Result<TeamProjectsResponse> {
+        let url = format!("{}/v1/teams/{team_id}/projects", self.base_url);
+
+        tracing::info!("Fetching projects for team: {}", team_id);
+
+        let response = self.send(|client| client.get(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<TeamProjectsResponse>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse projects response: {e}")))
+    }
+
+    /// List the files of a project
+    pub async fn get_project_files(&self, project_id: &str) -> Result<ProjectFilesResponse> {
+        let url = format!("{}/v1/projects/{project_id}/files", self.base_url);
+
+        tracing::info!("Fetching files for project: {}", project_id);
+
+        let response = self.send(|client| client.get(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<ProjectFilesResponse>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse project files response: {e}")))
+    }
+
+    /// Register a webhook (v2 API)
+    pub async fn create_webhook(&self, webhook: &NewWebhook) -> Result<Webhook> {
+        let url = format!("{}/v2/webhooks", self.base_url);
+
+        tracing::info!(
+            "Creating {} webhook for {} {}",
+            webhook.event_type,
+            webhook.context,
+            webhook.context_id
+        );
+
+        let response = self.send(|client| client.post(&url).json(webhook)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<Webhook>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse webhook response: {e}")))
+    }
+
+    /// List the webhooks of a team, project or file (v2 API)
+    pub async fn list_webhooks(&self, context: &str, context_id: &str) -> Result<Vec<Webhook>> {
+        let url = format!("{}/v2/webhooks", self.base_url);
+
+        tracing::info!("Fetching webhooks for {} {}", context, context_id);
+
+        let response = self
+            .send(|client| {
+                client
+                    .get(&url)
+                    .query(&[("context", context), ("context_id", context_id)])
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        response
+            .json::<WebhooksResponse>()
+            .await
+            .map(|r| r.webhooks)
+            .map_err(|e| Error::parse(format!("Failed to parse webhooks response: {e}")))
+    }
+
+    /// Delete a webhook (v2 API)
+    pub async fn delete_webhook(&self, webhook_id: &str) -> Result<()> {
+        let url = format!("{}/v2/webhooks/{webhook_id}", self.base_url);
+
+        tracing::info!("Deleting webhook: {}", webhook_id);
+
+        let response = self.send(|client| client.delete(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
     /// Get image URLs for specific nodes
     pub async fn get_images(
         &self,
@@ -250,28 +646,20 @@ impl FigmaClient {
         format: &str,
         scale: f64,
     ) -> Result<ImageResponse> {
-        let url = format!("{FIGMA_API_BASE}/images/{file_key}");
+        let url = format!("{}/v1/images/{file_key}", self.base_url);
         let ids = node_ids.join(",");
 
         tracing::info!("Requesting images for {} nodes", node_ids.len());
 
-        let response = retry_with_backoff(
-            || async {
-                self.client
-                    .get(&url)
-                    .header("X-Figma-Token", &self.token)
-                    .query(&[
-                        ("ids", ids.as_str()),
-                        ("format", format),
-                        ("scale", &scale.to_string()),
-                    ])
-                    .send()
-                    .await
-                    .map_err(|e| Error::network(format!("Request failed: {e}")))
-            },
-            self.retry_config,
-        )
-        .await?;
+        let response = self
+            .send(|client| {
+                client.get(&url).query(&[
+                    ("ids", ids.as_str()),
+                    ("format", format),
+                    ("scale", &scale.to_string()),
+                ])
+            })
+            .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_error_response(response).await);
@@ -283,9 +671,64 @@ impl FigmaClient {
             .map_err(|e| Error::parse(format!("Failed to parse image response: {e}")))
     }
 
+    /// Get download URLs for the original bitmaps behind image fills, keyed by `imageRef`
+    pub async fn get_image_fills(
+        &self,
+        file_key: &str,
+    ) -> Result<std::collections::HashMap<String, String>> {
+        let url = format!("{}/v1/files/{file_key}/images", self.base_url);
+
+        tracing::info!("Requesting image fills for file: {}", file_key);
+
+        let response = self.send(|client| client.get(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_error_response(response).await);
+        }
+
+        let fills = response
+            .json::<ImageFillsResponse>()
+            .await
+            .map_err(|e| Error::parse(format!("Failed to parse image fills response: {e}")))?;
+
+        Ok(fills
+            .meta
+            .images
+            .into_iter()
+            .filter_map(|(image_ref, url)| {
+                if url.is_none() {
+                    tracing::warn!("Image fill '{}' has no download URL", image_ref);
+                }
+                url.map(|url| (image_ref, url))
+            })
+            .collect())
+    }
+
+    /// Download a rendered asset from a URL returned by the images endpoint
+    pub async fn download(&self, url: &str) -> Result<bytes::Bytes> {
+        let response = self
+            .scheduler
+            .send(|| self.client.get(url), self.retry_config)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Error::http(
+                response.status().as_u16(),
+                "Failed to download image",
+                url,
+            ));
+        }
+
+        response
+            .bytes()
+            .await
+            .map_err(|e| Error::network(format!("Failed to read image bytes: {e}")))
+    }
+
     /// Handle error responses with detailed error info
     async fn handle_error_response(&self, response: reqwest::Response) -> Error {
         let status = response.status();
+        let endpoint = response.url().path().to_string();
 
         if status.as_u16() == 401 {
             return Error::Auth("Invalid or expired token".to_string());
@@ -295,27 +738,24 @@ impl FigmaClient {
             return Error::auth("Access denied. Check file permissions.");
         }
 
-        if status.as_u16() == 404 {
-            return Error::not_found("File not found");
+        // Figma's JSON error body carries the reason, e.g. {"status": 400, "err": "..."}
+        let body = response.text().await.unwrap_or_default();
+        if status.as_u16() == 400 && body.contains("Request too large") {
+            return Error::other(
+                "Request too large. Use --depth parameter to limit response size (try --depth 3 or lower)",
+            );
         }
 
-        if status.as_u16() == 429 {
-            return Error::RateLimit;
-        }
+        let message = self.credential.read().redact(&error_message(&body));
+        from_status_code(status.as_u16(), message, &endpoint)
+    }
+}
 
-        // Try to get error message from response body
-        match response.text().await {
-            Ok(body) => {
-                if status.as_u16() == 400 && body.contains("Request too large") {
-                    Error::other(
-                        "Request too large. Use --depth parameter to limit response size (try --depth 3 or lower)".to_string()
-                    )
-                } else {
-                    Error::other(format!("API error ({status}): {body}"))
-                }
-            }
-            Err(_) => Error::other(format!("API error: {status}")),
-        }
+/// Comments endpoint of a file, or of a single comment
+pub fn comments_url(base_url: &str, file_key: &str, comment_id: Option<&str>) -> String {
+    match comment_id {
+        Some(id) => format!("{base_url}/v1/files/{file_key}/comments/{id}"),
+        None => format!("{base_url}/v1/files/{file_key}/comments"),
     }
 }
 
@@ -336,6 +776,231 @@ pub struct ImageResponse {
     pub images: std::collections::HashMap<String, Option<String>>,
 }
 
+/// Image fills response from Figma API
+#[derive(Debug, serde::Deserialize)]
+pub struct ImageFillsResponse {
+    pub meta: ImageFillsMeta,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ImageFillsMeta {
+    #[serde(default)]
+    pub images: std::collections::HashMap<String, Option<String>>,
+}
+
+/// Version history page from Figma API
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct VersionsResponse {
+    pub versions: Vec<FileVersion>,
+    #[serde(default)]
+    pub pagination: Pagination,
+}
+
+/// A saved version of a file
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct FileVersion {
+    pub id: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub user: User,
+}
+
+/// A Figma user as embedded in versions and comments
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct User {
+    pub id: String,
+    pub handle: String,
+    #[serde(default)]
+    pub img_url: Option<String>,
+}
+
+/// Comments response from Figma API
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct CommentsResponse {
+    pub comments: Vec<Comment>,
+}
+
+/// A comment or reply on a file
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Comment {
+    pub id: String,
+    /// Id of the comment this replies to; absent or empty for top-level comments
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    pub user: User,
+    pub message: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub client_meta: Option<ClientMeta>,
+    /// Pin number shown in the Figma UI
+    #[serde(default)]
+    pub order_id: Option<String>,
+}
+
+impl Comment {
+    pub fn parent(&self) -> Option<&str> {
+        self.parent_id.as_deref().filter(|id| !id.is_empty())
+    }
+}
+
+/// Request body for posting a comment or reply
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NewComment {
+    pub message: String,
+    /// Comment being replied to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_meta: Option<ClientMeta>,
+}
+
+impl NewComment {
+    pub const fn new(message: String) -> Self {
+        Self {
+            message,
+            comment_id: None,
+            client_meta: None,
+        }
+    }
+
+    /// Pin the comment to the top-left corner of a node
+    pub fn with_node(mut self, node_id: String) -> Self {
+        self.client_meta = Some(ClientMeta {
+            node_id: Some(node_id),
+            node_offset: Some(crate::models::document::Vector2 { x: 0.0, y: 0.0 }),
+            ..ClientMeta::default()
+        });
+        self
+    }
+
+    pub fn with_reply_to(mut self, comment_id: String) -> Self {
+        self.comment_id = Some(comment_id);
+        self
+    }
+}
+
+/// Where a comment is pinned; `node_id` is set for comments anchored to a node
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct ClientMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_offset: Option<crate::models::document::Vector2>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<f64>,
+}
+
+/// The version-identifying fields of a file response
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMeta {
+    pub name: String,
+    pub version: String,
+    pub last_modified: chrono::DateTime<chrono::Utc>,
+}
+
+/// File response with `branch_data=true`, reduced to its branches
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct BranchesResponse {
+    pub name: String,
+    #[serde(default)]
+    pub branches: Vec<Branch>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Branch {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+    pub last_modified: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub link_url: Option<String>,
+}
+
+/// Request body for registering a webhook
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NewWebhook {
+    pub event_type: String,
+    /// `team`, `project` or `file`
+    pub context: String,
+    pub context_id: String,
+    pub endpoint: String,
+    pub passcode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Webhooks response from Figma API
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebhooksResponse {
+    pub webhooks: Vec<Webhook>,
+}
+
+/// A registered webhook; the passcode is not kept
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Webhook {
+    pub id: String,
+    pub event_type: String,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub context_id: Option<String>,
+    pub endpoint: String,
+    pub status: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Team projects response from Figma API
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TeamProjectsResponse {
+    /// Team name
+    pub name: String,
+    pub projects: Vec<Project>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+}
+
+/// Project files response from Figma API
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct ProjectFilesResponse {
+    /// Project name
+    pub name: String,
+    pub files: Vec<ProjectFile>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ProjectFile {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+    pub last_modified: chrono::DateTime<chrono::Utc>,
+}
+
+/// Links to neighbouring pages of a paginated response
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Pagination {
+    #[serde(default)]
+    pub prev_page: Option<String>,
+    #[serde(default)]
+    pub next_page: Option<String>,
+}
+
 /// Nodes response from Figma API
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct NodesResponse {
@@ -369,4 +1034,220 @@ mod tests {
 
         assert_eq!(client.retry_config.max_retries, 5);
     }
+
+    #[test]
+    fn test_custom_base_url() {
+        let client = FigmaClient::new("figd_test_token".to_string())
+            .unwrap()
+            .with_base_url("http://127.0.0.1:8080/");
+
+        assert_eq!(client.base_url, "http://127.0.0.1:8080");
+    }
+
+    mod http_errors {
+        use super::*;
+        use serde_json::json;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const FILE_KEY: &str = "ABCDEFGHIJKLMNOPQRSTUV";
+
+        fn file_path() -> String {
+            format!("/v1/files/{FILE_KEY}")
+        }
+
+        fn file_body() -> serde_json::Value {
+            json!({
+                "name": "Checkout",
+                "version": "1",
+                "lastModified": "2025-01-15T10:30:00Z",
+                "editorType": "figma",
+                "document": { "id": "0:0", "name": "Document", "type": "DOCUMENT", "children": [] }
+            })
+        }
+
+        /// Client with its own scheduler and short backoff, plus its scheduler for counters
+        fn client(server: &MockServer, timeout_secs: u64) -> (FigmaClient, Arc<Scheduler>) {
+            let scheduler = Arc::new(Scheduler::default());
+            let client = FigmaClient::with_timeout("figd_test_token".to_string(), timeout_secs)
+                .unwrap()
+                .with_base_url(server.uri())
+                .with_retry_config(RetryConfig::new().with_base_delay(10))
+                .with_scheduler(Arc::clone(&scheduler));
+            (client, scheduler)
+        }
+
+        #[tokio::test]
+        async fn test_retries_transient_server_errors() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path(file_path()))
+                .respond_with(ResponseTemplate::new(502))
+                .up_to_n_times(2)
+                .with_priority(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path(file_path()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(file_body()))
+                .mount(&server)
+                .await;
+
+            let (client, scheduler) = client(&server, 30);
+            let file = client.get_file(FILE_KEY, None, None).await.unwrap();
+
+            assert_eq!(file.name, "Checkout");
+            assert_eq!(scheduler.stats().retries, 2);
+        }
+
+        #[tokio::test]
+        async fn test_persistent_server_error_is_typed() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path(file_path()))
+                .respond_with(
+                    ResponseTemplate::new(503)
+                        .set_body_json(json!({ "status": 503, "err": "Service unavailable" })),
+                )
+                .expect(4)
+                .mount(&server)
+                .await;
+
+            let (client, _) = client(&server, 30);
+            let err = client.get_file(FILE_KEY, None, None).await.unwrap_err();
+
+            match err {
+                Error::Http {
+                    status,
+                    body,
+                    endpoint,
+                } => {
+                    assert_eq!(status, 503);
+                    assert_eq!(body, "Service unavailable");
+                    assert_eq!(endpoint, file_path());
+                }
+                other => panic!("unexpected error: {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_posts_are_not_retried_on_server_errors() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path(format!("{}/comments", file_path())))
+                .respond_with(
+                    ResponseTemplate::new(502)
+                        .set_body_json(json!({ "status": 502, "err": "Bad gateway" })),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let (client, scheduler) = client(&server, 30);
+            let err = client
+                .post_comment(FILE_KEY, &NewComment::new("Looks good".to_string()))
+                .await
+                .unwrap_err();
+
+            assert!(matches!(err, Error::Http { status: 502, .. }), "{err:?}");
+            assert_eq!(scheduler.stats().retries, 0);
+        }
+
+        #[tokio::test]
+        async fn test_client_errors_are_not_retried() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path(file_path()))
+                .respond_with(
+                    ResponseTemplate::new(400)
+                        .set_body_json(json!({ "status": 400, "err": "Invalid parameter" })),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let (client, _) = client(&server, 30);
+            let err = client.get_file(FILE_KEY, None, None).await.unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                format!("HTTP 400 from {}: Invalid parameter", file_path())
+            );
+        }
+
+        #[tokio::test]
+        async fn test_retries_timeouts() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path(file_path()))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(file_body())
+                        .set_delay(Duration::from_secs(3)),
+                )
+                .up_to_n_times(1)
+                .with_priority(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path(file_path()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(file_body()))
+                .mount(&server)
+                .await;
+
+            let (client, scheduler) = client(&server, 1);
+            let file = client.get_file(FILE_KEY, None, None).await.unwrap();
+
+            assert_eq!(file.name, "Checkout");
+            assert_eq!(scheduler.stats().retries, 1);
+        }
+
+        #[tokio::test]
+        async fn test_file_and_node_not_found() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/v1/files/MISSING/nodes"))
+                .respond_with(
+                    ResponseTemplate::new(404)
+                        .set_body_json(json!({ "status": 404, "err": "Not found" })),
+                )
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path(format!("{}/nodes", file_path())))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "name": "Checkout",
+                    "nodes": {
+                        "1:2": { "document": { "id": "1:2", "name": "Cart", "type": "FRAME" } },
+                        "9:9": null
+                    }
+                })))
+                .mount(&server)
+                .await;
+
+            let (client, _) = client(&server, 30);
+
+            let err = client
+                .get_nodes("MISSING", &["1:2".to_string()], None, None)
+                .await
+                .unwrap_err();
+            assert_eq!(err.to_string(), "not found: file MISSING");
+
+            let ids = ["1:2".to_string(), "9:9".to_string()];
+            let err = client
+                .get_nodes(FILE_KEY, &ids, None, None)
+                .await
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("not found: node 9:9 in file {FILE_KEY}")
+            );
+
+            let nodes = client
+                .get_nodes(FILE_KEY, &ids[..1], None, None)
+                .await
+                .unwrap();
+            assert!(nodes.nodes["1:2"].is_some());
+        }
+    }
 }
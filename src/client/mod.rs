@@ -2,8 +2,14 @@ pub mod auth;
 pub mod error;
 pub mod figma;
 pub mod retry;
+pub mod scheduler;
 
-pub use auth::TokenManager;
+pub use auth::{Credential, OAuthToken, Profile, TokenManager};
 pub use error::Result;
-pub use figma::{FigmaClient, ImageResponse, UserInfo};
-pub use retry::{RetryConfig, retry_with_backoff};
+pub use figma::{
+    Branch, BranchesResponse, Comment, FigmaClient, FileMeta, FileVersion, ImageResponse,
+    NewComment, NewWebhook, Project, ProjectFile, ProjectFilesResponse, RefreshHook,
+    TeamProjectsResponse, User, UserInfo, VersionsResponse, Webhook, comments_url,
+};
+pub use retry::RetryConfig;
+pub use scheduler::{RateLimits, Scheduler, SchedulerStats, Tier};
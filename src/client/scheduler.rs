@@ -0,0 +1,470 @@
+//! Request scheduling shared by every client: per-tier rate budgets, a global concurrency cap and
+//! `429` handling
+
+use crate::client::error::{Result, is_retryable_request, is_retryable_status};
+use crate::client::retry::{RetryConfig, backoff_delay};
+use crate::core::errors::Error;
+use parking_lot::Mutex;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, RequestBuilder, Response, StatusCode, Url};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::time::sleep;
+
+/// Longest `Retry-After` waited out; beyond this the `429` is returned to the caller
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(600);
+
+static GLOBAL: OnceLock<Arc<Scheduler>> = OnceLock::new();
+
+/// Figma's rate limit tier of an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    /// File contents, nodes and rendered images
+    One,
+    /// Comments, versions, projects, image fills, webhooks and the rest
+    Two,
+    /// Metadata, the current user and library listings
+    Three,
+    /// Asset downloads outside the API; not budgeted but still counted against concurrency
+    Download,
+}
+
+impl Tier {
+    /// Tier of a request URL
+    pub fn for_url(url: &Url) -> Self {
+        let path = url.path();
+        let Some(start) = path.find("/v1/").or_else(|| path.find("/v2/")) else {
+            return Self::Download;
+        };
+        let segments: Vec<&str> = path[start + 4..]
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        match segments.as_slice() {
+            ["files", _] | ["files", _, "nodes"] | ["images", _] => Self::One,
+            ["me"]
+            | [
+                "files",
+                _,
+                "meta" | "components" | "component_sets" | "styles",
+            ]
+            | ["components" | "component_sets" | "styles", ..] => Self::Three,
+            _ => Self::Two,
+        }
+    }
+
+    const fn index(self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Two => 1,
+            Self::Three => 2,
+            Self::Download => 3,
+        }
+    }
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => f.write_str("tier 1"),
+            Self::Two => f.write_str("tier 2"),
+            Self::Three => f.write_str("tier 3"),
+            Self::Download => f.write_str("downloads"),
+        }
+    }
+}
+
+/// Request budgets per minute for each tier, and the cap on requests in flight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimits {
+    pub tier1_per_minute: u32,
+    pub tier2_per_minute: u32,
+    pub tier3_per_minute: u32,
+    pub max_concurrent: usize,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            tier1_per_minute: 15,
+            tier2_per_minute: 50,
+            tier3_per_minute: 100,
+            max_concurrent: 10,
+        }
+    }
+}
+
+/// Token bucket holding up to a minute's budget, refilled continuously
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    per_second: f64,
+    tokens: f64,
+    refilled: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(per_minute: u32, now: Instant) -> Self {
+        let capacity = f64::from(per_minute.max(1));
+        Self {
+            capacity,
+            per_second: capacity / 60.0,
+            tokens: capacity,
+            refilled: now,
+            paused_until: None,
+        }
+    }
+
+    /// Reserve a token, returning how long to wait before it may be used
+    fn take(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = elapsed
+            .mul_add(self.per_second, self.tokens)
+            .min(self.capacity);
+        self.refilled = now;
+        self.tokens -= 1.0;
+
+        let refill = if self.tokens < 0.0 {
+            Duration::from_secs_f64(-self.tokens / self.per_second)
+        } else {
+            Duration::ZERO
+        };
+        let paused = self
+            .paused_until
+            .map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        refill.max(paused)
+    }
+
+    /// Hold every request until `until`, as the server asked
+    fn pause(&mut self, until: Instant) {
+        self.paused_until = Some(self.paused_until.map_or(until, |p| p.max(until)));
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests: [AtomicU64; 4],
+    rate_limited: AtomicU64,
+    retries: AtomicU64,
+    waited_ms: AtomicU64,
+}
+
+/// Snapshot of what the scheduler has done so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchedulerStats {
+    pub tier1: u64,
+    pub tier2: u64,
+    pub tier3: u64,
+    pub downloads: u64,
+    /// Responses with status `429`
+    pub rate_limited: u64,
+    pub retries: u64,
+    /// Time spent waiting for rate budgets and `Retry-After`
+    pub waited: Duration,
+}
+
+impl SchedulerStats {
+    pub const fn total(&self) -> u64 {
+        self.tier1 + self.tier2 + self.tier3 + self.downloads
+    }
+}
+
+impl fmt::Display for SchedulerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "requests: {} (tier 1: {}, tier 2: {}, tier 3: {}, downloads: {}), rate limited: {}, retries: {}, waited: {:.1}s",
+            self.total(),
+            self.tier1,
+            self.tier2,
+            self.tier3,
+            self.downloads,
+            self.rate_limited,
+            self.retries,
+            self.waited.as_secs_f64()
+        )
+    }
+}
+
+/// Paces requests to Figma's per-tier rate limits and caps how many run at once
+pub struct Scheduler {
+    buckets: [Mutex<Bucket>; 3],
+    permits: Semaphore,
+    counters: Counters,
+}
+
+impl Scheduler {
+    pub fn new(limits: RateLimits) -> Self {
+        let now = Instant::now();
+        Self {
+            buckets: [
+                Mutex::new(Bucket::new(limits.tier1_per_minute, now)),
+                Mutex::new(Bucket::new(limits.tier2_per_minute, now)),
+                Mutex::new(Bucket::new(limits.tier3_per_minute, now)),
+            ],
+            permits: Semaphore::new(limits.max_concurrent.max(1)),
+            counters: Counters::default(),
+        }
+    }
+
+    /// Process-wide scheduler, created with default limits unless [`Self::init_global`] ran first
+    pub fn global() -> Arc<Self> {
+        Self::init_global(RateLimits::default())
+    }
+
+    /// Process-wide scheduler, created with `limits` if it does not exist yet
+    pub fn init_global(limits: RateLimits) -> Arc<Self> {
+        Arc::clone(GLOBAL.get_or_init(|| Arc::new(Self::new(limits))))
+    }
+
+    /// Counters so far
+    pub fn stats(&self) -> SchedulerStats {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let requests = &self.counters.requests;
+        SchedulerStats {
+            tier1: load(&requests[0]),
+            tier2: load(&requests[1]),
+            tier3: load(&requests[2]),
+            downloads: load(&requests[3]),
+            rate_limited: load(&self.counters.rate_limited),
+            retries: load(&self.counters.retries),
+            waited: Duration::from_millis(load(&self.counters.waited_ms)),
+        }
+    }
+
+    /// Send a request within its tier's budget, retrying `429`s, and for idempotent methods
+    /// also transient server errors, timeouts and dropped connections
+    ///
+    /// A `429` pauses the whole tier for exactly its `Retry-After`; without one, the usual
+    /// backoff applies. Once retries run out, the last response is returned as is.
+    pub async fn send(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+        retry: RetryConfig,
+    ) -> Result<Response> {
+        let mut attempt = 0;
+
+        loop {
+            let (client, built) = request().build_split();
+            let built = built.map_err(|e| Error::network(format!("Invalid request: {e}")))?;
+            let tier = Tier::for_url(built.url());
+            // Repeating a POST that reached Figma could create a second comment or webhook
+            let idempotent = matches!(
+                *built.method(),
+                Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+            );
+
+            self.wait_for_budget(tier).await;
+            let result = {
+                let _permit = self
+                    .permits
+                    .acquire()
+                    .await
+                    .map_err(|e| Error::other(format!("Scheduler closed: {e}")))?;
+                self.counters.requests[tier.index()].fetch_add(1, Ordering::Relaxed);
+                client.execute(built).await
+            };
+
+            let delay = match result {
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    self.counters.rate_limited.fetch_add(1, Ordering::Relaxed);
+                    let delay = retry_after(response.headers())
+                        .unwrap_or_else(|| backoff_delay(&retry, attempt));
+                    if attempt >= retry.max_retries || delay > MAX_RETRY_AFTER {
+                        return Ok(response);
+                    }
+
+                    let header = |name: &str| {
+                        response
+                            .headers()
+                            .get(name)
+                            .and_then(|v| v.to_str().ok())
+                            .unwrap_or("unknown")
+                            .to_string()
+                    };
+                    tracing::warn!(
+                        "Rate limited on {} (limit type: {}, plan tier: {}). Retrying in {:.1}s...",
+                        tier,
+                        header("x-figma-rate-limit-type"),
+                        header("x-figma-plan-tier"),
+                        delay.as_secs_f64()
+                    );
+                    if let Some(bucket) = self.bucket(tier) {
+                        bucket.lock().pause(Instant::now() + delay);
+                        // The paused bucket makes the next attempt wait
+                        Duration::ZERO
+                    } else {
+                        delay
+                    }
+                }
+                Ok(response)
+                    if idempotent
+                        && is_retryable_status(response.status().as_u16())
+                        && attempt < retry.max_retries =>
+                {
+                    let delay = retry_after(response.headers())
+                        .filter(|delay| *delay <= MAX_RETRY_AFTER)
+                        .unwrap_or_else(|| backoff_delay(&retry, attempt));
+                    tracing::warn!(
+                        "Server returned {} for {} (attempt {}/{}). Retrying in {}ms...",
+                        response.status(),
+                        response.url().path(),
+                        attempt + 1,
+                        retry.max_retries,
+                        delay.as_millis()
+                    );
+                    delay
+                }
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    let retryable = idempotent && is_retryable_request(&e);
+                    let error = if e.is_timeout() {
+                        Error::network(format!("Request timed out: {e}"))
+                    } else {
+                        Error::network(format!("Request failed: {e}"))
+                    };
+                    if !retryable || attempt >= retry.max_retries {
+                        return Err(error);
+                    }
+
+                    let delay = backoff_delay(&retry, attempt);
+                    tracing::warn!(
+                        "Request failed (attempt {}/{}): {}. Retrying in {}ms...",
+                        attempt + 1,
+                        retry.max_retries,
+                        error,
+                        delay.as_millis()
+                    );
+                    delay
+                }
+            };
+
+            self.wait(delay).await;
+            self.counters.retries.fetch_add(1, Ordering::Relaxed);
+            attempt += 1;
+        }
+    }
+
+    fn bucket(&self, tier: Tier) -> Option<&Mutex<Bucket>> {
+        self.buckets.get(tier.index())
+    }
+
+    async fn wait_for_budget(&self, tier: Tier) {
+        if let Some(bucket) = self.bucket(tier) {
+            let delay = bucket.lock().take(Instant::now());
+            if !delay.is_zero() {
+                tracing::debug!("Waiting {:.1}s for {} budget", delay.as_secs_f64(), tier);
+            }
+            self.wait(delay).await;
+        }
+    }
+
+    async fn wait(&self, delay: Duration) {
+        if delay.is_zero() {
+            return;
+        }
+        let millis = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+        self.counters.waited_ms.fetch_add(millis, Ordering::Relaxed);
+        sleep(delay).await;
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new(RateLimits::default())
+    }
+}
+
+/// Delay asked for by a `Retry-After` header, in seconds or as an HTTP date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, RETRY_AFTER};
+
+    fn tier(path: &str) -> Tier {
+        Tier::for_url(&Url::parse(&format!("https://api.figma.com{path}")).unwrap())
+    }
+
+    #[test]
+    fn test_tier_for_url() {
+        assert_eq!(tier("/v1/files/KEY"), Tier::One);
+        assert_eq!(tier("/v1/files/KEY/nodes"), Tier::One);
+        assert_eq!(tier("/v1/images/KEY"), Tier::One);
+        assert_eq!(tier("/v1/files/KEY/images"), Tier::Two);
+        assert_eq!(tier("/v1/files/KEY/comments"), Tier::Two);
+        assert_eq!(tier("/v1/teams/1/projects"), Tier::Two);
+        assert_eq!(tier("/v2/webhooks"), Tier::Two);
+        assert_eq!(tier("/v1/me"), Tier::Three);
+        assert_eq!(tier("/v1/files/KEY/meta"), Tier::Three);
+        assert_eq!(tier("/proxy/v1/files/KEY"), Tier::One);
+        assert_eq!(tier("/images/abc/def"), Tier::Download);
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_paces() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(60, start);
+
+        for _ in 0..60 {
+            assert_eq!(bucket.take(start), Duration::ZERO);
+        }
+        assert_eq!(bucket.take(start), Duration::from_secs(1));
+        assert_eq!(bucket.take(start), Duration::from_secs(2));
+
+        // Two seconds later both reserved tokens have been refilled and used
+        let later = start + Duration::from_secs(2);
+        assert_eq!(bucket.take(later), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_bucket_pause() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(100, start);
+
+        bucket.pause(start + Duration::from_secs(5));
+        bucket.pause(start + Duration::from_secs(3));
+        assert_eq!(bucket.take(start), Duration::from_secs(5));
+        assert_eq!(bucket.take(start + Duration::from_secs(6)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -17,6 +17,9 @@ pub struct CacheEntry {
     pub created_at: DateTime<Utc>,
     pub accessed_at: DateTime<Utc>,
     pub depth: Option<u32>,
+    /// Fetched with an explicit `version` rather than as the latest
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[derive(Debug)]
@@ -35,6 +38,8 @@ pub struct CacheMetadata {
     pub accessed_at: DateTime<Utc>,
     pub depth: Option<u32>,
     pub size: u64,
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 impl Cache {
@@ -58,8 +63,9 @@ impl Cache {
         &self,
         file_key: &str,
         depth: Option<u32>,
+        at_version: Option<&str>,
     ) -> Result<Option<serde_json::Value>> {
-        let cache_key = self.cache_key(file_key, depth);
+        let cache_key = self.cache_key(file_key, depth, at_version);
         let path = self.entry_path(&cache_key);
 
         if !path.exists() {
@@ -83,8 +89,9 @@ impl Cache {
         version: &str,
         data: &serde_json::Value,
         depth: Option<u32>,
+        at_version: Option<&str>,
     ) -> Result<()> {
-        let cache_key = self.cache_key(file_key, depth);
+        let cache_key = self.cache_key(file_key, depth, at_version);
         let entry = CacheEntry {
             file_key: file_key.to_string(),
             version: version.to_string(),
@@ -92,6 +99,7 @@ impl Cache {
             created_at: Utc::now(),
             accessed_at: Utc::now(),
             depth,
+            pinned: at_version.is_some(),
         };
 
         self.write_entry(&cache_key, &entry)?;
@@ -103,8 +111,9 @@ impl Cache {
         file_key: &str,
         node_ids: &[String],
         depth: Option<u32>,
+        at_version: Option<&str>,
     ) -> Result<Option<serde_json::Value>> {
-        let cache_key = self.nodes_cache_key(file_key, node_ids, depth);
+        let cache_key = self.nodes_cache_key(file_key, node_ids, depth, at_version);
         let path = self.entry_path(&cache_key);
 
         if !path.exists() {
@@ -127,16 +136,18 @@ impl Cache {
         file_key: &str,
         node_ids: &[String],
         depth: Option<u32>,
+        at_version: Option<&str>,
         data: &serde_json::Value,
     ) -> Result<()> {
-        let cache_key = self.nodes_cache_key(file_key, node_ids, depth);
+        let cache_key = self.nodes_cache_key(file_key, node_ids, depth, at_version);
         let entry = CacheEntry {
             file_key: file_key.to_string(),
-            version: String::new(),
+            version: at_version.unwrap_or_default().to_string(),
             data: data.clone(),
             created_at: Utc::now(),
             accessed_at: Utc::now(),
             depth,
+            pinned: at_version.is_some(),
         };
 
         self.write_entry(&cache_key, &entry)?;
@@ -160,6 +171,33 @@ impl Cache {
         self.index.read().values().cloned().collect()
     }
 
+    /// Version of the most recently cached latest copy of a file
+    pub fn cached_version(&self, file_key: &str) -> Option<String> {
+        self.index
+            .read()
+            .values()
+            .filter(|m| m.file_key == file_key && !m.pinned && !m.version.is_empty())
+            .max_by_key(|m| m.created_at)
+            .map(|m| m.version.clone())
+    }
+
+    /// Drop latest copies of a file, except those already at `keep`
+    pub fn evict_latest(&self, file_key: &str, keep: Option<&str>) -> Result<usize> {
+        let stale: Vec<String> = self
+            .index
+            .read()
+            .values()
+            .filter(|m| m.file_key == file_key && !m.pinned && Some(m.version.as_str()) != keep)
+            .map(|m| m.cache_key.clone())
+            .collect();
+
+        for key in &stale {
+            self.remove_entry(key)?;
+        }
+
+        Ok(stale.len())
+    }
+
     pub fn stats(&self) -> CacheStats {
         let index = self.index.read();
         let total_entries = index.len();
@@ -182,7 +220,8 @@ impl Cache {
         }
     }
 
-    fn cache_key(&self, file_key: &str, depth: Option<u32>) -> String {
+    /// Key for a whole-file entry; pinned versions never share a key with the latest
+    fn cache_key(&self, file_key: &str, depth: Option<u32>, at_version: Option<&str>) -> String {
         let mut hasher = Hasher::new();
         hasher.update(b"file:");
         hasher.update(file_key.as_bytes());
@@ -190,10 +229,20 @@ impl Cache {
             hasher.update(b":depth:");
             hasher.update(d.to_string().as_bytes());
         }
+        if let Some(v) = at_version {
+            hasher.update(b":version:");
+            hasher.update(v.as_bytes());
+        }
         hasher.finalize().to_hex().to_string()
     }
 
-    fn nodes_cache_key(&self, file_key: &str, node_ids: &[String], depth: Option<u32>) -> String {
+    fn nodes_cache_key(
+        &self,
+        file_key: &str,
+        node_ids: &[String],
+        depth: Option<u32>,
+        at_version: Option<&str>,
+    ) -> String {
         let mut hasher = Hasher::new();
         hasher.update(b"nodes:");
         hasher.update(file_key.as_bytes());
@@ -205,6 +254,10 @@ impl Cache {
             hasher.update(b":depth:");
             hasher.update(d.to_string().as_bytes());
         }
+        if let Some(v) = at_version {
+            hasher.update(b":version:");
+            hasher.update(v.as_bytes());
+        }
         hasher.finalize().to_hex().to_string()
     }
 
@@ -239,6 +292,7 @@ impl Cache {
             accessed_at: entry.accessed_at,
             depth: entry.depth,
             size: data.len() as u64,
+            pinned: entry.pinned,
         };
 
         self.index.write().insert(cache_key.to_string(), metadata);
@@ -328,3 +382,49 @@ pub struct CacheStats {
     pub expired_entries: usize,
     pub ttl_hours: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pinned_version_has_own_entry() {
+        let dir = TempDir::new().unwrap();
+        let cache = Cache::new(dir.path().to_path_buf(), 24).unwrap();
+
+        let latest = serde_json::json!({ "version": "2" });
+        let pinned = serde_json::json!({ "version": "1" });
+        cache.put_file("key", "2", &latest, None, None).unwrap();
+        cache
+            .put_file("key", "1", &pinned, None, Some("1"))
+            .unwrap();
+
+        assert_eq!(cache.get_file("key", None, None).unwrap(), Some(latest));
+        assert_eq!(
+            cache.get_file("key", None, Some("1")).unwrap(),
+            Some(pinned)
+        );
+        assert_eq!(cache.get_file("key", None, Some("3")).unwrap(), None);
+        assert_eq!(cache.list().len(), 2);
+    }
+
+    #[test]
+    fn test_evict_stale_keeps_pinned_and_current() {
+        let dir = TempDir::new().unwrap();
+        let cache = Cache::new(dir.path().to_path_buf(), 24).unwrap();
+
+        let data = serde_json::json!({});
+        cache.put_file("key", "1", &data, None, None).unwrap();
+        cache.put_file("key", "1", &data, None, Some("1")).unwrap();
+        cache
+            .put_nodes("key", &["1:2".to_string()], None, None, &data)
+            .unwrap();
+        assert_eq!(cache.cached_version("key").as_deref(), Some("1"));
+
+        assert_eq!(cache.evict_latest("key", Some("2")).unwrap(), 2);
+        assert_eq!(cache.get_file("key", None, None).unwrap(), None);
+        assert!(cache.get_file("key", None, Some("1")).unwrap().is_some());
+        assert_eq!(cache.cached_version("key"), None);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::core::constants::{
+    CREDENTIALS_FILE_NAME, DEFAULT_PROFILE, ENV_API_BASE, FIGMA_API_BASE,
+};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -5,7 +8,13 @@ use zeroize::Zeroize;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct Config {
-    pub token: Option<String>,
+    /// Token from `FIGMA_TOKEN` or the command line; stored tokens live in the credentials file
+    /// and one found here is only read to migrate it, never written back
+    #[serde(default, skip_serializing)]
+    pub token: Option<SecureString>,
+
+    /// Default team for `projects` and `files`
+    pub team: Option<String>,
 
     #[serde(default)]
     pub extraction: ExtractionConfig,
@@ -21,6 +30,9 @@ pub struct Config {
 
     #[serde(default)]
     pub cache: CacheConfig,
+
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -57,6 +69,9 @@ pub struct HttpConfig {
 
     #[serde(default = "default_backoff")]
     pub backoff: f64,
+
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -85,11 +100,29 @@ pub struct CacheConfig {
     pub path: Option<PathBuf>,
 }
 
+/// Requests per minute for each Figma rate limit tier, shared by all commands in one process
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RateLimitConfig {
+    #[serde(default = "default_tier1_per_minute")]
+    pub tier1_per_minute: u32,
+
+    #[serde(default = "default_tier2_per_minute")]
+    pub tier2_per_minute: u32,
+
+    #[serde(default = "default_tier3_per_minute")]
+    pub tier3_per_minute: u32,
+
+    /// Requests in flight at once, downloads included
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent: usize,
+}
+
 pub use CacheConfig as Cache;
 pub use ExtractionConfig as Extraction;
 pub use HttpConfig as Http;
 pub use ImageConfig as Images;
 pub use PerformanceConfig as Performance;
+pub use RateLimitConfig as RateLimit;
 const fn default_depth() -> u32 {
     5
 }
@@ -120,6 +153,9 @@ const fn default_max_delay() -> u64 {
 const fn default_backoff() -> f64 {
     2.0
 }
+fn default_base_url() -> String {
+    FIGMA_API_BASE.to_string()
+}
 const fn default_scale() -> f32 {
     2.0
 }
@@ -135,6 +171,18 @@ const fn default_chunk_size() -> usize {
 const fn default_ttl() -> u64 {
     24
 }
+const fn default_tier1_per_minute() -> u32 {
+    15
+}
+const fn default_tier2_per_minute() -> u32 {
+    50
+}
+const fn default_tier3_per_minute() -> u32 {
+    100
+}
+const fn default_max_concurrent_requests() -> usize {
+    10
+}
 
 impl Default for ExtractionConfig {
     fn default() -> Self {
@@ -156,6 +204,7 @@ impl Default for HttpConfig {
             retry_delay: default_retry_delay(),
             max_delay: default_max_delay(),
             backoff: default_backoff(),
+            base_url: default_base_url(),
         }
     }
 }
@@ -187,6 +236,17 @@ impl Default for CacheConfig {
     }
 }
 
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            tier1_per_minute: default_tier1_per_minute(),
+            tier2_per_minute: default_tier2_per_minute(),
+            tier3_per_minute: default_tier3_per_minute(),
+            max_concurrent: default_max_concurrent_requests(),
+        }
+    }
+}
+
 impl Config {
     /// Load config with default paths
     pub fn load() -> Result<Self> {
@@ -226,12 +286,15 @@ impl Config {
 
         // Override with environment
         if let Ok(token) = std::env::var("FIGMA_TOKEN") {
-            config.token = Some(token);
+            config.token = Some(SecureString::new(token));
+        }
+        if let Ok(base_url) = std::env::var(ENV_API_BASE) {
+            config.http.base_url = base_url;
         }
 
         // Override with CLI args
         if let Some(token) = cli_token {
-            config.token = Some(token);
+            config.token = Some(SecureString::new(token));
         }
         if let Some(dir) = cli_cache_dir {
             config.cache.path = Some(dir);
@@ -271,6 +334,11 @@ impl Config {
         Self::default_config_path()
     }
 
+    /// Credentials file next to the global config
+    pub fn credentials_path() -> Option<PathBuf> {
+        Self::default_config_path().map(|p| p.with_file_name(CREDENTIALS_FILE_NAME))
+    }
+
     /// Get default cache directory
     pub fn default_cache_dir() -> Option<PathBuf> {
         dirs::cache_dir().map(|mut p| {
@@ -292,6 +360,16 @@ impl Config {
             .unwrap_or_else(|| PathBuf::from("/tmp/figma-cli"))
     }
 
+    /// Cache directory for a profile; the default profile keeps the top-level directory
+    pub fn profile_cache_path(&self, profile: &str) -> PathBuf {
+        let dir = self.cache_path();
+        if profile == DEFAULT_PROFILE {
+            dir
+        } else {
+            dir.join("profiles").join(profile)
+        }
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> Result<()> {
         if self.extraction.depth > self.extraction.max_depth {
@@ -302,10 +380,39 @@ impl Config {
             anyhow::bail!("timeout must be > 0");
         }
 
+        let Ok(base_url) = url::Url::parse(&self.http.base_url) else {
+            anyhow::bail!("invalid base_url: {}", self.http.base_url);
+        };
+        // `/v1` and `/v2` are added per request, so an old-style `.../v1` would double them
+        let path = base_url.path().trim_end_matches('/');
+        if path.ends_with("/v1") || path.ends_with("/v2") {
+            anyhow::bail!(
+                "base_url must not include the API version: {} (try {})",
+                self.http.base_url,
+                self.http
+                    .base_url
+                    .trim_end_matches('/')
+                    .trim_end_matches("/v1")
+                    .trim_end_matches("/v2")
+            );
+        }
+
         if self.images.scale <= 0.0 || self.images.scale > 4.0 {
             anyhow::bail!("scale must be 0 < scale <= 4");
         }
 
+        let limits = &self.rate_limit;
+        if [
+            limits.tier1_per_minute,
+            limits.tier2_per_minute,
+            limits.tier3_per_minute,
+        ]
+        .contains(&0)
+            || limits.max_concurrent == 0
+        {
+            anyhow::bail!("rate_limit values must be > 0");
+        }
+
         if !["png", "jpg", "svg", "pdf"].contains(&self.images.format.as_str()) {
             anyhow::bail!("invalid format: {}", self.images.format);
         }
@@ -315,19 +422,16 @@ impl Config {
 
     /// Display configuration (optionally as JSON)
     pub fn show(&self, json: bool) -> Result<()> {
-        let mut masked = self.clone();
-        if let Some(token) = &masked.token {
-            masked.token = Some(mask_token(token));
-        }
-
         if json {
-            println!("{}", serde_json::to_string_pretty(&masked)?);
+            println!("{}", serde_json::to_string_pretty(self)?);
         } else {
-            println!("token: {}", masked.token.as_deref().unwrap_or("-"));
-            println!("extraction.depth: {}", masked.extraction.depth);
-            println!("http.timeout: {}s", masked.http.timeout);
-            println!("images.scale: {}x", masked.images.scale);
-            println!("images.format: {}", masked.images.format);
+            let token = self.token.as_ref().map(|t| mask_token(t.as_str()));
+            println!("token: {}", token.as_deref().unwrap_or("-"));
+            println!("extraction.depth: {}", self.extraction.depth);
+            println!("http.timeout: {}s", self.http.timeout);
+            println!("http.base_url: {}", self.http.base_url);
+            println!("images.scale: {}x", self.images.scale);
+            println!("images.format: {}", self.images.format);
         }
 
         Ok(())
@@ -365,10 +469,16 @@ fn mask_token(token: &str) -> String {
     }
 }
 
-/// Secure string that zeros memory on drop
-#[derive(Debug, Clone)]
+/// Secure string that zeros memory on drop and never shows up in `Debug` output
+#[derive(Clone, PartialEq, Eq)]
 pub struct SecureString(String);
 
+impl std::fmt::Debug for SecureString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
 impl SecureString {
     pub const fn new(s: String) -> Self {
         Self(s)
@@ -3,8 +3,11 @@
 //! This module centralizes all constants to avoid magic numbers/strings
 //! and ensure consistency across the codebase.
 // Network Constants
-/// Figma API base URL
-pub const FIGMA_API_BASE: &str = "https://api.figma.com/v1";
+/// Figma API base URL (version prefixes such as `/v1` are appended per request)
+pub const FIGMA_API_BASE: &str = "https://api.figma.com";
+
+/// Figma page where users authorize OAuth apps
+pub const FIGMA_OAUTH_URL: &str = "https://www.figma.com/oauth";
 
 /// Figma API token header name
 pub const FIGMA_TOKEN_HEADER: &str = "X-Figma-Token";
@@ -80,6 +83,12 @@ pub const APP_NAME: &str = "figma-cli";
 /// Configuration file name
 pub const CONFIG_FILE_NAME: &str = "config.toml";
 
+/// Credentials file name, next to the global config
+pub const CREDENTIALS_FILE_NAME: &str = "credentials.toml";
+
+/// Profile used when none is selected
+pub const DEFAULT_PROFILE: &str = "default";
+
 /// Project configuration file name
 pub const PROJECT_CONFIG_FILE: &str = "figma-cli.toml";
 
@@ -126,6 +135,9 @@ pub const MAX_DISPLAY_WIDTH: usize = 120;
 /// Environment variable for Figma API token
 pub const ENV_FIGMA_TOKEN: &str = "FIGMA_TOKEN";
 
+/// Environment variable for Figma API base URL
+pub const ENV_API_BASE: &str = "FIGMA_API_BASE";
+
 /// Environment variable for HTTP timeout
 pub const ENV_HTTP_TIMEOUT: &str = "FIGMA_HTTP_TIMEOUT";
 
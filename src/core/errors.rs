@@ -34,8 +34,15 @@ pub enum Error {
     #[error("toml serialize error: {0}")]
     TomlSerialize(#[from] toml::ser::Error),
 
-    #[error("http error: {0}")]
-    Http(#[from] reqwest::Error),
+    #[error("request error: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("HTTP {status} from {endpoint}: {body}")]
+    Http {
+        status: u16,
+        body: String,
+        endpoint: String,
+    },
 
     #[error("{0}")]
     Other(String),
@@ -65,6 +72,24 @@ impl Error {
         Self::NotFound(msg.into())
     }
 
+    pub fn http(status: u16, body: impl Into<String>, endpoint: impl Into<String>) -> Self {
+        Self::Http {
+            status,
+            body: body.into(),
+            endpoint: endpoint.into(),
+        }
+    }
+
+    /// The file itself does not exist or is not visible with the current credentials
+    pub fn file_not_found(file_key: &str) -> Self {
+        Self::NotFound(format!("file {file_key}"))
+    }
+
+    /// The file exists but some requested nodes do not
+    pub fn node_not_found(file_key: &str, node_ids: &[&str]) -> Self {
+        Self::NotFound(format!("node {} in file {file_key}", node_ids.join(", ")))
+    }
+
     pub fn io(msg: impl Into<String>) -> Self {
         Self::Other(msg.into())
     }
@@ -0,0 +1,185 @@
+//! Design element extraction
+
+use crate::models::document::{Node, NodeData};
+use crate::models::extraction::{DesignElement, Dimensions, HierarchyPath};
+use crate::service::traversal::NodeVisitor;
+
+#[derive(Default)]
+pub struct ElementExtractor {
+    elements: Vec<DesignElement>,
+}
+
+impl ElementExtractor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_elements(self) -> Vec<DesignElement> {
+        self.elements
+    }
+
+    pub fn count(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+impl NodeVisitor for ElementExtractor {
+    fn visit_node(&mut self, node: &Node, _depth: usize, path: &[&Node]) {
+        if !matches!(
+            &node.data,
+            NodeData::Frame { .. }
+                | NodeData::Component { .. }
+                | NodeData::ComponentSet { .. }
+                | NodeData::Instance { .. }
+                | NodeData::Section { .. }
+                | NodeData::Group { .. }
+        ) {
+            return;
+        }
+
+        let dimensions = node.absolute_bounding_box().map(Dimensions::from);
+
+        self.elements.push(DesignElement {
+            id: node.id().to_string(),
+            name: node.name().to_string(),
+            element_type: node.node_type_str().to_string(),
+            path: HierarchyPath::from_ancestors(path),
+            dimensions,
+            child_count: node.children().map_or(0, <[Node]>::len),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::document::{Appearance, BoundingBox, Layout, NodeBase};
+    use std::collections::HashMap;
+
+    fn create_node(node_type: &str, id: &str, data: NodeData) -> Node {
+        Node {
+            base: NodeBase {
+                node_type: node_type.to_string(),
+                id: id.to_string(),
+                name: format!("{node_type} {id}"),
+                visible: true,
+                locked: false,
+            },
+            data,
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    fn create_frame(id: &str, children: Vec<Node>) -> Node {
+        create_node(
+            "FRAME",
+            id,
+            NodeData::Frame {
+                absolute_bounding_box: Some(BoundingBox {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 375.0,
+                    height: 812.0,
+                }),
+                fills: vec![],
+                clips_content: false,
+                layout: Layout::default(),
+                appearance: Appearance::default(),
+                children,
+            },
+        )
+    }
+
+    fn create_page(name: &str) -> Node {
+        let mut page = create_node(
+            "CANVAS",
+            "0:1",
+            NodeData::Canvas {
+                background_color: None,
+                export_settings: vec![],
+                children: vec![],
+            },
+        );
+        page.base.name = name.to_string();
+        page
+    }
+
+    #[test]
+    fn test_frame_extraction() {
+        let mut extractor = ElementExtractor::new();
+        let page = create_page("Page 1");
+        let path = vec![&page];
+
+        let text = create_node(
+            "TEXT",
+            "1:2",
+            NodeData::Text {
+                characters: "Hi".to_string(),
+                absolute_bounding_box: None,
+                fills: vec![],
+                style: None,
+                character_style_overrides: vec![],
+                style_override_table: HashMap::new(),
+                appearance: Appearance::default(),
+            },
+        );
+        extractor.visit_node(&create_frame("1:1", vec![text]), 2, &path);
+
+        let elements = extractor.into_elements();
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].element_type, "FRAME");
+        assert_eq!(elements[0].child_count, 1);
+        assert_eq!(elements[0].path.page_name, "Page 1");
+
+        let dims = elements[0].dimensions.unwrap();
+        assert!((dims.width - 375.0).abs() < f64::EPSILON);
+        assert!((dims.height - 812.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_skip_non_container_nodes() {
+        let mut extractor = ElementExtractor::new();
+        let page = create_page("Page 1");
+        let path = vec![&page];
+
+        let text = create_node(
+            "TEXT",
+            "1:1",
+            NodeData::Text {
+                characters: "Hi".to_string(),
+                absolute_bounding_box: None,
+                fills: vec![],
+                style: None,
+                character_style_overrides: vec![],
+                style_override_table: HashMap::new(),
+                appearance: Appearance::default(),
+            },
+        );
+        extractor.visit_node(&text, 2, &path);
+
+        assert_eq!(extractor.count(), 0);
+    }
+
+    #[test]
+    fn test_group_without_bounds() {
+        let mut extractor = ElementExtractor::new();
+        let page = create_page("Page 1");
+        let path = vec![&page];
+
+        let group = create_node(
+            "GROUP",
+            "3:1",
+            NodeData::Group {
+                absolute_bounding_box: None,
+                appearance: Appearance::default(),
+                children: vec![],
+            },
+        );
+        extractor.visit_node(&group, 2, &path);
+
+        let elements = extractor.into_elements();
+        assert_eq!(elements[0].element_type, "GROUP");
+        assert!(elements[0].dimensions.is_none());
+        assert_eq!(elements[0].child_count, 0);
+    }
+}
@@ -1,5 +1,7 @@
 //! Content extraction from Figma documents
 
+pub mod element;
 pub mod text;
 
+pub use element::ElementExtractor;
 pub use text::TextExtractor;
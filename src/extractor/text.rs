@@ -1,8 +1,11 @@
 //! Text node extraction
 
-use crate::models::document::{Node, NodeData};
-use crate::models::extraction::{ExtractedText, HierarchyPath, TextNodeType, TextStyleInfo};
+use crate::models::document::{Node, NodeData, TypeStyle};
+use crate::models::extraction::{
+    ExtractedText, HierarchyPath, StyledRun, TextNodeType, TextStyleInfo,
+};
 use crate::service::traversal::NodeVisitor;
+use std::collections::HashMap;
 
 #[derive(Default)]
 pub struct TextExtractor {
@@ -25,18 +28,35 @@ impl TextExtractor {
 }
 
 impl NodeVisitor for TextExtractor {
-    fn visit_node(&mut self, node: &Node, _depth: usize, path: &[String]) {
-        let (node_type, characters, style) = match &node.data {
+    fn visit_node(&mut self, node: &Node, _depth: usize, path: &[&Node]) {
+        let (node_type, characters, style, runs) = match &node.data {
             NodeData::Text {
-                characters, style, ..
-            } => (TextNodeType::Text, characters.as_str(), style.as_ref()),
+                characters,
+                style,
+                character_style_overrides,
+                style_override_table,
+                ..
+            } => (
+                TextNodeType::Text,
+                characters.as_str(),
+                style.as_ref(),
+                styled_runs(
+                    characters,
+                    style.as_ref(),
+                    character_style_overrides,
+                    style_override_table,
+                ),
+            ),
             NodeData::Sticky { characters, .. } => {
-                (TextNodeType::Sticky, characters.as_str(), None)
+                (TextNodeType::Sticky, characters.as_str(), None, Vec::new())
+            }
+            NodeData::ShapeWithText { characters, .. } | NodeData::Connector { characters, .. } => {
+                (TextNodeType::Text, characters.as_str(), None, Vec::new())
             }
             NodeData::Other {
                 characters: Some(chars),
                 ..
-            } if !chars.trim().is_empty() => (TextNodeType::Text, chars.as_str(), None),
+            } if !chars.trim().is_empty() => (TextNodeType::Text, chars.as_str(), None, Vec::new()),
             _ => return,
         };
 
@@ -44,62 +64,82 @@ impl NodeVisitor for TextExtractor {
             return;
         }
 
-        let style_info = style.map(|s| TextStyleInfo {
-            font_family: s
-                .font_family
-                .clone()
-                .unwrap_or_else(|| "Unknown".to_string()),
-            font_size: s.font_size.unwrap_or(16.0),
-            font_weight: s.font_weight.unwrap_or(400),
-        });
+        let style_info = style.map(TextStyleInfo::from);
 
         self.texts.push(ExtractedText {
             node_id: node.id().to_string(),
             node_type,
             text: characters.to_string(),
-            path: build_hierarchy_path(path),
+            path: HierarchyPath::from_ancestors(path),
             sequence_number: self.sequence_number,
             style: style_info,
+            runs,
         });
         self.sequence_number += 1;
     }
 }
 
-fn build_hierarchy_path(path: &[String]) -> HierarchyPath {
-    let mut iter = path.iter().skip(1);
+/// Split text into runs of identical style
+///
+/// Figma indexes `characterStyleOverrides` by UTF-16 code unit and drops
+/// trailing zeros; id 0 means the node's base style.
+fn styled_runs(
+    characters: &str,
+    base: Option<&TypeStyle>,
+    overrides: &[usize],
+    table: &HashMap<String, TypeStyle>,
+) -> Vec<StyledRun> {
+    if overrides.iter().all(|&id| id == 0) {
+        return Vec::new();
+    }
 
-    let page_name = iter
-        .next()
-        .cloned()
-        .unwrap_or_else(|| "Unknown".to_string());
+    let base = base.cloned().unwrap_or_default();
+    let resolve = |id: usize| match table.get(&id.to_string()) {
+        Some(style) if id != 0 => TextStyleInfo::from(&base.merged(style)),
+        _ => TextStyleInfo::from(&base),
+    };
 
-    let mut section_name = None;
-    let mut frame_names = Vec::new();
+    let mut runs = Vec::new();
+    let mut current: Option<(usize, usize, String)> = None;
+    let mut utf16_offset = 0;
 
-    for name in iter {
-        if section_name.is_none() && is_section_name(name) {
-            section_name = Some(name.clone());
-        } else {
-            frame_names.push(name.clone());
+    for (index, ch) in characters.chars().enumerate() {
+        let id = overrides.get(utf16_offset).copied().unwrap_or(0);
+        utf16_offset += ch.len_utf16();
+
+        match &mut current {
+            Some((run_id, _, text)) if *run_id == id => text.push(ch),
+            _ => {
+                if let Some((run_id, start, text)) = current.take() {
+                    runs.push(StyledRun {
+                        start,
+                        end: index,
+                        text,
+                        style: resolve(run_id),
+                    });
+                }
+                current = Some((id, index, ch.to_string()));
+            }
         }
     }
 
-    HierarchyPath {
-        page_name,
-        section_name,
-        frame_names,
-        group_names: None,
+    if let Some((run_id, start, text)) = current {
+        let end = start + text.chars().count();
+        runs.push(StyledRun {
+            start,
+            end,
+            text,
+            style: resolve(run_id),
+        });
     }
-}
 
-fn is_section_name(name: &str) -> bool {
-    name.to_lowercase().contains("section") || name.contains(" > ") || name.starts_with("> ")
+    runs
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::document::{NodeBase, TypeStyle};
+    use crate::models::document::{Appearance, Layout, NodeBase, TypeStyle};
 
     fn create_text_node(id: &str, text: &str) -> Node {
         Node {
@@ -113,26 +153,70 @@ mod tests {
             data: NodeData::Text {
                 characters: text.to_string(),
                 absolute_bounding_box: None,
+                fills: vec![],
                 style: Some(TypeStyle {
                     font_family: Some("Inter".to_string()),
                     font_size: Some(16.0),
                     font_weight: Some(400),
+                    ..TypeStyle::default()
                 }),
+                character_style_overrides: vec![],
+                style_override_table: HashMap::new(),
+                appearance: Appearance::default(),
+            },
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    fn create_container(node_type: &str, name: &str, data: NodeData) -> Node {
+        Node {
+            base: NodeBase {
+                node_type: node_type.to_string(),
+                id: "0:1".to_string(),
+                name: name.to_string(),
+                visible: true,
+                locked: false,
             },
+            data,
+            extra: serde_json::Map::new(),
         }
     }
 
+    fn create_page(name: &str) -> Node {
+        create_container(
+            "CANVAS",
+            name,
+            NodeData::Canvas {
+                background_color: None,
+                export_settings: vec![],
+                children: vec![],
+            },
+        )
+    }
+
+    fn create_frame(name: &str) -> Node {
+        create_container(
+            "FRAME",
+            name,
+            NodeData::Frame {
+                absolute_bounding_box: None,
+                fills: vec![],
+                clips_content: false,
+                layout: Layout::default(),
+                appearance: Appearance::default(),
+                children: vec![],
+            },
+        )
+    }
+
     #[test]
     fn test_text_extraction() {
         let mut extractor = TextExtractor::new();
-        let path = vec![
-            "Document".to_string(),
-            "Page 1".to_string(),
-            "Frame 1".to_string(),
-        ];
+        let page = create_page("Page 1");
+        let frame = create_frame("Frame 1");
 
         let node = create_text_node("1:1", "Hello, World!");
-        extractor.visit_node(&node, 2, &path);
+        extractor.visit_node(&node, 2, &[&page, &frame]);
 
         let texts = extractor.into_texts();
         assert_eq!(texts.len(), 1);
@@ -144,10 +228,8 @@ mod tests {
     #[test]
     fn test_skip_empty_text() {
         let mut extractor = TextExtractor::new();
-        let path = vec!["Document".to_string()];
-
         let node = create_text_node("1:1", "   ");
-        extractor.visit_node(&node, 1, &path);
+        extractor.visit_node(&node, 1, &[]);
 
         assert_eq!(extractor.count(), 0);
     }
@@ -155,11 +237,9 @@ mod tests {
     #[test]
     fn test_sequence_numbers() {
         let mut extractor = TextExtractor::new();
-        let path = vec!["Document".to_string()];
-
-        extractor.visit_node(&create_text_node("1:1", "First"), 1, &path);
-        extractor.visit_node(&create_text_node("1:2", "Second"), 1, &path);
-        extractor.visit_node(&create_text_node("1:3", "Third"), 1, &path);
+        extractor.visit_node(&create_text_node("1:1", "First"), 1, &[]);
+        extractor.visit_node(&create_text_node("1:2", "Second"), 1, &[]);
+        extractor.visit_node(&create_text_node("1:3", "Third"), 1, &[]);
 
         let texts = extractor.into_texts();
         assert_eq!(texts[0].sequence_number, 0);
@@ -170,12 +250,22 @@ mod tests {
     #[test]
     fn test_sticky_extraction() {
         let mut extractor = TextExtractor::new();
-        let path = vec![
-            "Document".to_string(),
-            "Page 1".to_string(),
-            "Section > Features".to_string(),
-            "Frame 1".to_string(),
-        ];
+        let page = create_page("Page 1");
+        let section = create_container(
+            "SECTION",
+            "Features",
+            NodeData::Section {
+                absolute_bounding_box: None,
+                absolute_render_bounds: None,
+                fills: vec![],
+                strokes: vec![],
+                stroke_weight: 0.0,
+                stroke_align: String::new(),
+                section_contents_hidden: false,
+                children: vec![],
+            },
+        );
+        let frame = create_frame("Section header");
 
         let sticky = Node {
             base: NodeBase {
@@ -190,18 +280,80 @@ mod tests {
                 absolute_bounding_box: None,
                 fills: vec![],
             },
+            extra: serde_json::Map::new(),
         };
 
-        extractor.visit_node(&sticky, 2, &path);
+        extractor.visit_node(&sticky, 3, &[&page, &section, &frame]);
 
         let texts = extractor.into_texts();
         assert_eq!(texts.len(), 1);
         assert_eq!(texts[0].node_type, TextNodeType::Sticky);
         assert_eq!(texts[0].text, "TODO: Review this implementation");
-        assert_eq!(
-            texts[0].path.section_name,
-            Some("Section > Features".to_string())
-        );
+        assert_eq!(texts[0].path.section_name, Some("Features".to_string()));
+        assert_eq!(texts[0].path.frame_names, vec!["Section header"]);
         assert!(texts[0].style.is_none());
     }
+
+    fn create_styled_node(text: &str, overrides: Vec<usize>) -> Node {
+        let mut node = create_text_node("1:1", text);
+        if let NodeData::Text {
+            character_style_overrides,
+            style_override_table,
+            ..
+        } = &mut node.data
+        {
+            *character_style_overrides = overrides;
+            style_override_table.insert(
+                "7".to_string(),
+                TypeStyle {
+                    font_weight: Some(700),
+                    italic: Some(true),
+                    ..TypeStyle::default()
+                },
+            );
+        }
+        node
+    }
+
+    #[test]
+    fn test_styled_runs() {
+        let mut extractor = TextExtractor::new();
+        // "Buy now today" with "now" in bold italic; trailing zeros omitted
+        let node = create_styled_node("Buy now today", vec![0, 0, 0, 0, 7, 7, 7]);
+        extractor.visit_node(&node, 1, &[]);
+
+        let texts = extractor.into_texts();
+        let runs = &texts[0].runs;
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[1].text, "now");
+        assert_eq!((runs[1].start, runs[1].end), (4, 7));
+        assert_eq!(runs[1].style.font_weight, 700);
+        assert_eq!(runs[1].style.italic, Some(true));
+        assert_eq!(runs[1].style.font_family, "Inter");
+        assert_eq!(runs[2].text, " today");
+        assert_eq!(runs[2].style.font_weight, 400);
+    }
+
+    #[test]
+    fn test_styled_runs_count_utf16_units() {
+        let mut extractor = TextExtractor::new();
+        // The emoji occupies two override slots
+        let node = create_styled_node("🎉 Go", vec![0, 0, 0, 7, 7]);
+        extractor.visit_node(&node, 1, &[]);
+
+        let texts = extractor.into_texts();
+        let runs = &texts[0].runs;
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].text, "🎉 ");
+        assert_eq!(runs[1].text, "Go");
+        assert_eq!((runs[1].start, runs[1].end), (2, 4));
+    }
+
+    #[test]
+    fn test_uniform_style_has_no_runs() {
+        let mut extractor = TextExtractor::new();
+        extractor.visit_node(&create_styled_node("Plain", vec![0, 0]), 1, &[]);
+
+        assert!(extractor.into_texts()[0].runs.is_empty());
+    }
 }
@@ -2,14 +2,13 @@
 
 use anyhow::{Context, Result};
 use base64::{Engine as _, engine::general_purpose};
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::client::FigmaClient;
 use crate::core::config::Images;
 
 pub struct ImageProcessor {
-    client: Client,
     config: Images,
 }
 
@@ -29,26 +28,17 @@ pub struct ImageResult {
     pub scale: f64,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct FigmaImageResponse {
-    pub err: Option<String>,
-    pub images: HashMap<String, Option<String>>,
-}
-
 impl ImageProcessor {
-    pub fn new(config: Images) -> Result<Self> {
-        Ok(Self {
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()?,
-            config,
-        })
+    pub const fn new(config: Images) -> Self {
+        Self { config }
     }
 
     /// Process frame images from Figma API
+    ///
+    /// Requests go through `client`, so OAuth tokens are refreshed and rate limits honoured.
     pub async fn process_frames(
         &self,
-        token: &str,
+        client: &FigmaClient,
         file_key: &str,
         node_ids: &[String],
         base64: bool,
@@ -58,14 +48,14 @@ impl ImageProcessor {
         }
 
         // Get image URLs from Figma API
-        let image_urls = self.fetch_image_urls(token, file_key, node_ids).await?;
+        let image_urls = self.fetch_image_urls(client, file_key, node_ids).await?;
 
         let mut results = Vec::new();
 
         for (node_id, url) in image_urls {
             let result = if base64 {
                 // Download and convert to base64
-                self.process_as_base64(&node_id, &url).await?
+                self.process_as_base64(client, &node_id, &url).await?
             } else {
                 // Return URL only
                 ImageResult {
@@ -86,37 +76,20 @@ impl ImageProcessor {
     /// Fetch image URLs from Figma API
     async fn fetch_image_urls(
         &self,
-        token: &str,
+        client: &FigmaClient,
         file_key: &str,
         node_ids: &[String],
     ) -> Result<HashMap<String, String>> {
-        let ids = node_ids.join(",");
-        let url = format!(
-            "https://api.figma.com/v1/images/{}?ids={}&format={}&scale={}",
-            file_key, ids, self.config.format, self.config.scale
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .header("X-Figma-Token", token)
-            .send()
+        let figma_response = client
+            .get_images(
+                file_key,
+                node_ids,
+                &self.config.format,
+                f64::from(self.config.scale),
+            )
             .await
             .context("Failed to fetch images from Figma")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "Figma API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            );
-        }
-
-        let figma_response: FigmaImageResponse = response
-            .json()
-            .await
-            .context("Failed to parse Figma image response")?;
-
         if let Some(err) = figma_response.err {
             anyhow::bail!("Figma API error: {err}");
         }
@@ -144,8 +117,16 @@ impl ImageProcessor {
     }
 
     /// Process image as base64
-    async fn process_as_base64(&self, node_id: &str, url: &str) -> Result<ImageResult> {
-        let bytes = self.download_image(url).await?;
+    async fn process_as_base64(
+        &self,
+        client: &FigmaClient,
+        node_id: &str,
+        url: &str,
+    ) -> Result<ImageResult> {
+        let bytes = client
+            .download(url)
+            .await
+            .context("Failed to download image")?;
         let base64_str = general_purpose::STANDARD.encode(&bytes);
 
         Ok(ImageResult {
@@ -156,26 +137,6 @@ impl ImageProcessor {
             scale: f64::from(self.config.scale),
         })
     }
-
-    /// Download image from URL
-    async fn download_image(&self, url: &str) -> Result<Vec<u8>> {
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .context("Failed to download image")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to download image: {}", response.status());
-        }
-
-        response
-            .bytes()
-            .await
-            .map(|b| b.to_vec())
-            .context("Failed to read image bytes")
-    }
 }
 
 /// Helper for AI agent integration
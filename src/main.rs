@@ -4,9 +4,12 @@ use anyhow::Result;
 use clap::Parser;
 use figma_cli::cli::{Cli, Commands};
 use figma_cli::cli::{
-    handle_auth, handle_cache, handle_config, handle_extract, handle_images, handle_inspect,
-    handle_query,
+    handle_auth, handle_branches, handle_cache, handle_comments, handle_config, handle_diff,
+    handle_extract, handle_extract_batch, handle_files, handle_images, handle_inspect,
+    handle_projects, handle_query, handle_serve_webhooks, handle_versions, handle_watch,
+    handle_webhooks,
 };
+use figma_cli::client::{Scheduler, TokenManager};
 
 fn init_logging(verbose: bool) {
     use tracing_subscriber::{EnvFilter, fmt};
@@ -26,13 +29,33 @@ async fn main() -> Result<()> {
 
     init_logging(cli.verbose);
 
-    match cli.command {
+    if let Some(profile) = cli.profile {
+        TokenManager::select_profile(profile)?;
+    }
+
+    let result = match cli.command {
         Commands::Extract(args) => handle_extract(args).await,
+        Commands::ExtractBatch(args) => handle_extract_batch(args).await,
+        Commands::Watch(args) => handle_watch(args).await,
         Commands::Inspect(args) => handle_inspect(args).await,
         Commands::Images(args) => handle_images(args).await,
         Commands::Query(args) => handle_query(args).await,
+        Commands::Versions(args) => handle_versions(args).await,
+        Commands::Branches(args) => handle_branches(args).await,
+        Commands::Diff(args) => handle_diff(args).await,
+        Commands::Comments(args) => handle_comments(args.command).await,
+        Commands::ServeWebhooks(args) => handle_serve_webhooks(args).await,
+        Commands::Webhooks(args) => handle_webhooks(args.command).await,
+        Commands::Projects(args) => handle_projects(args).await,
+        Commands::Files(args) => handle_files(args).await,
         Commands::Cache(args) => handle_cache(args.command).await,
         Commands::Auth(args) => handle_auth(args.command).await,
         Commands::Config(args) => handle_config(args.command).await,
+    };
+
+    if cli.verbose {
+        tracing::info!("{}", Scheduler::global().stats());
     }
+
+    result
 }
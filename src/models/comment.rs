@@ -0,0 +1,116 @@
+//! Comment report structures
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::extraction::HierarchyPath;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentReport {
+    pub file_key: String,
+    pub file_name: String,
+    pub threads: Vec<CommentThread>,
+}
+
+/// A top-level comment with its replies in chronological order
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentThread {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<String>,
+    pub author: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<CommentAnchor>,
+    pub replies: Vec<CommentReply>,
+}
+
+/// The node a comment is pinned to
+///
+/// `name` and `path` are absent when the node no longer exists in the file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentAnchor {
+    pub node_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<HierarchyPath>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentReply {
+    pub id: String,
+    pub author: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Thread filter; every criterion is checked against the top-level comment
+#[derive(Debug, Clone, Default)]
+pub struct CommentFilter {
+    pub resolved: Option<bool>,
+    pub author: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl CommentFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub const fn with_resolved(mut self, resolved: bool) -> Self {
+        self.resolved = Some(resolved);
+        self
+    }
+
+    pub fn with_author(mut self, author: String) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    pub const fn with_since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub const fn with_until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn matches(&self, thread: &CommentThread) -> bool {
+        if let Some(resolved) = self.resolved
+            && thread.resolved_at.is_some() != resolved
+        {
+            return false;
+        }
+
+        if let Some(author) = &self.author
+            && !thread.author.eq_ignore_ascii_case(author)
+        {
+            return false;
+        }
+
+        if let Some(since) = self.since
+            && thread.created_at < since
+        {
+            return false;
+        }
+
+        if let Some(until) = self.until
+            && thread.created_at >= until
+        {
+            return false;
+        }
+
+        true
+    }
+}
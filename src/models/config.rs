@@ -1,6 +1,8 @@
 //! Configuration structures for extraction
 
+use crate::core::errors::Error;
 use regex::Regex;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Default)]
 pub struct FilterCriteria {
@@ -139,6 +141,23 @@ impl ImageFormat {
     }
 }
 
+impl FromStr for ImageFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Self::Png),
+            "jpg" | "jpeg" => Ok(Self::Jpg),
+            "svg" => Ok(Self::Svg),
+            "pdf" => Ok(Self::Pdf),
+            other => Err(Error::validation(
+                "image_format",
+                format!("Unsupported image format '{other}'. Expected png, jpg, svg or pdf"),
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,4 +219,12 @@ mod tests {
         assert_eq!(ImageFormat::Svg.mime_type(), "image/svg+xml");
         assert_eq!(ImageFormat::Pdf.mime_type(), "application/pdf");
     }
+
+    #[test]
+    fn test_image_format_from_str() {
+        assert_eq!("png".parse::<ImageFormat>().unwrap(), ImageFormat::Png);
+        assert_eq!("JPEG".parse::<ImageFormat>().unwrap(), ImageFormat::Jpg);
+        assert_eq!("svg".parse::<ImageFormat>().unwrap(), ImageFormat::Svg);
+        assert!("gif".parse::<ImageFormat>().is_err());
+    }
 }
@@ -0,0 +1,116 @@
+//! Diff output structures
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::document::{Paint, TypeStyle};
+use super::extraction::{Dimensions, HierarchyPath};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    pub file_key: String,
+    pub file_name: String,
+    pub from: DiffSide,
+    pub to: DiffSide,
+    pub summary: DiffSummary,
+    pub nodes: Vec<NodeDiff>,
+}
+
+/// One of the two files being compared
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSide {
+    pub version: String,
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Counts per kind of change; added and removed count subtree roots only
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub moved: usize,
+    pub renamed: usize,
+    pub characters: usize,
+    pub fills: usize,
+    pub size: usize,
+    pub style: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeStatus {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A node that differs between the two files
+///
+/// `path` is the node's location in the newer file, or in the older one for removals.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeDiff {
+    pub node_id: String,
+    /// Old id when the node was matched by path instead of id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_id: Option<String>,
+    pub node_type: String,
+    pub name: String,
+    pub path: HierarchyPath,
+    pub status: NodeStatus,
+    /// Nodes added or removed together with this one
+    #[serde(skip_serializing_if = "is_zero")]
+    pub descendants: usize,
+    /// Text of an added or removed text node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub characters: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Change {
+    Moved {
+        from: HierarchyPath,
+        to: HierarchyPath,
+    },
+    Renamed {
+        from: String,
+        to: String,
+    },
+    Characters {
+        from: String,
+        to: String,
+    },
+    Fills {
+        from: Vec<Paint>,
+        to: Vec<Paint>,
+    },
+    Size {
+        from: Option<Dimensions>,
+        to: Option<Dimensions>,
+    },
+    Style {
+        from: Option<Box<TypeStyle>>,
+        to: Option<Box<TypeStyle>>,
+    },
+}
+
+impl NodeDiff {
+    /// Full location including the node itself
+    pub fn location(&self) -> String {
+        if self.node_type == "CANVAS" {
+            self.name.clone()
+        } else {
+            format!("{} > {}", self.path.to_path_string(), self.name)
+        }
+    }
+}
+
+const fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
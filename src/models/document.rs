@@ -4,7 +4,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -71,142 +71,155 @@ pub struct NodeBase {
 pub struct Node {
     pub base: NodeBase,
     pub data: NodeData,
+    /// Fields not modeled by `NodeData`, re-emitted verbatim on serialization
+    pub extra: Map<String, Value>,
 }
 
 /// Variant-specific data for each node type
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// The variant is chosen from `NodeBase::node_type` while deserializing;
+/// unknown types fall back to `Other`.
+#[derive(Debug, Clone)]
 pub enum NodeData {
     Canvas {
-        #[serde(rename = "backgroundColor", default, with = "option_struct")]
         background_color: Option<Color>,
-        #[serde(rename = "exportSettings", default)]
         export_settings: Vec<ExportSetting>,
-        #[serde(default)]
         children: Vec<Node>,
     },
     Section {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(rename = "absoluteRenderBounds", default, with = "option_struct")]
         absolute_render_bounds: Option<BoundingBox>,
-        #[serde(default)]
         fills: Vec<Paint>,
-        #[serde(default)]
         strokes: Vec<Paint>,
-        #[serde(rename = "strokeWeight", default)]
         stroke_weight: f64,
-        #[serde(rename = "strokeAlign", default)]
         stroke_align: String,
-        #[serde(rename = "sectionContentsHidden", default)]
         section_contents_hidden: bool,
-        #[serde(default)]
         children: Vec<Node>,
     },
     Frame {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
         fills: Vec<Paint>,
-        #[serde(rename = "clipsContent", default)]
         clips_content: bool,
-        #[serde(default)]
+        layout: Layout,
+        appearance: Appearance,
         children: Vec<Node>,
     },
     Group {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
+        appearance: Appearance,
         children: Vec<Node>,
     },
     Text {
-        #[serde(default)]
         characters: String,
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default, with = "option_struct")]
+        fills: Vec<Paint>,
         style: Option<TypeStyle>,
+        character_style_overrides: Vec<usize>,
+        style_override_table: HashMap<String, TypeStyle>,
+        appearance: Appearance,
     },
     Rectangle {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(rename = "cornerRadius", default)]
         corner_radius: f64,
-        #[serde(default)]
         fills: Vec<Paint>,
+        appearance: Appearance,
     },
     Vector {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
         fills: Vec<Paint>,
+        appearance: Appearance,
+    },
+    Ellipse {
+        absolute_bounding_box: Option<BoundingBox>,
+        fills: Vec<Paint>,
+        appearance: Appearance,
+    },
+    Line {
+        absolute_bounding_box: Option<BoundingBox>,
+        fills: Vec<Paint>,
+        appearance: Appearance,
+    },
+    Star {
+        absolute_bounding_box: Option<BoundingBox>,
+        fills: Vec<Paint>,
+        appearance: Appearance,
+    },
+    RegularPolygon {
+        absolute_bounding_box: Option<BoundingBox>,
+        fills: Vec<Paint>,
+        appearance: Appearance,
+    },
+    Slice {
+        absolute_bounding_box: Option<BoundingBox>,
+    },
+    Connector {
+        characters: String,
+        absolute_bounding_box: Option<BoundingBox>,
+        appearance: Appearance,
+    },
+    ShapeWithText {
+        characters: String,
+        shape_type: Option<String>,
+        absolute_bounding_box: Option<BoundingBox>,
+        fills: Vec<Paint>,
+        appearance: Appearance,
+    },
+    Stamp {
+        absolute_bounding_box: Option<BoundingBox>,
+    },
+    Widget {
+        absolute_bounding_box: Option<BoundingBox>,
+        children: Vec<Node>,
+    },
+    Embed {
+        absolute_bounding_box: Option<BoundingBox>,
     },
     Component {
-        #[serde(rename = "componentKey")]
         component_key: Option<String>,
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
+        layout: Layout,
+        appearance: Appearance,
         children: Vec<Node>,
     },
     ComponentSet {
-        #[serde(rename = "componentKey")]
         component_key: Option<String>,
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
+        layout: Layout,
+        appearance: Appearance,
         children: Vec<Node>,
     },
     Instance {
-        #[serde(rename = "componentId", default)]
         component_id: String,
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
+        layout: Layout,
+        appearance: Appearance,
         children: Vec<Node>,
     },
     Sticky {
-        #[serde(default)]
         characters: String,
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
         fills: Vec<Paint>,
     },
     BooleanOperation {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
         fills: Vec<Paint>,
-        #[serde(default)]
+        appearance: Appearance,
         children: Vec<Node>,
     },
     Table {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
         fills: Vec<Paint>,
-        #[serde(default)]
+        appearance: Appearance,
         children: Vec<Node>,
     },
     TableCell {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
         absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
         fills: Vec<Paint>,
-        #[serde(default)]
+        appearance: Appearance,
         children: Vec<Node>,
     },
-    Shape {
-        #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
-        absolute_bounding_box: Option<BoundingBox>,
-        #[serde(default)]
-        fills: Vec<Paint>,
-    },
     Other {
-        #[serde(default)]
         characters: Option<String>,
-        #[serde(default)]
         children: Vec<Node>,
     },
 }
@@ -238,6 +251,7 @@ impl Node {
             | NodeData::Section { children, .. }
             | NodeData::Frame { children, .. }
             | NodeData::Group { children, .. }
+            | NodeData::Widget { children, .. }
             | NodeData::Component { children, .. }
             | NodeData::ComponentSet { children, .. }
             | NodeData::Instance { children, .. }
@@ -251,14 +265,166 @@ impl Node {
 
     pub fn characters(&self) -> Option<&str> {
         match &self.data {
-            NodeData::Text { characters, .. } | NodeData::Sticky { characters, .. } => {
-                Some(characters)
-            }
+            NodeData::Text { characters, .. }
+            | NodeData::Sticky { characters, .. }
+            | NodeData::Connector { characters, .. }
+            | NodeData::ShapeWithText { characters, .. } => Some(characters),
             NodeData::Other { characters, .. } => characters.as_deref(),
             _ => None,
         }
     }
 
+    pub fn absolute_bounding_box(&self) -> Option<&BoundingBox> {
+        match &self.data {
+            NodeData::Section {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Frame {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Group {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Text {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Rectangle {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Vector {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Ellipse {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Line {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Star {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::RegularPolygon {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Slice {
+                absolute_bounding_box,
+            }
+            | NodeData::Connector {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::ShapeWithText {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Stamp {
+                absolute_bounding_box,
+            }
+            | NodeData::Widget {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Embed {
+                absolute_bounding_box,
+            }
+            | NodeData::Component {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::ComponentSet {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Instance {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Sticky {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::BooleanOperation {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::Table {
+                absolute_bounding_box,
+                ..
+            }
+            | NodeData::TableCell {
+                absolute_bounding_box,
+                ..
+            } => absolute_bounding_box.as_ref(),
+            NodeData::Canvas { .. } | NodeData::Other { .. } => None,
+        }
+    }
+
+    /// Auto-layout, constraint and grid properties of frame-like nodes
+    pub fn layout(&self) -> Option<&Layout> {
+        match &self.data {
+            NodeData::Frame { layout, .. }
+            | NodeData::Component { layout, .. }
+            | NodeData::ComponentSet { layout, .. }
+            | NodeData::Instance { layout, .. } => Some(layout),
+            _ => None,
+        }
+    }
+
+    /// Strokes, corner radii and effects, where the node type supports them
+    pub fn appearance(&self) -> Option<&Appearance> {
+        match &self.data {
+            NodeData::Frame { appearance, .. }
+            | NodeData::Group { appearance, .. }
+            | NodeData::Text { appearance, .. }
+            | NodeData::Rectangle { appearance, .. }
+            | NodeData::Vector { appearance, .. }
+            | NodeData::Ellipse { appearance, .. }
+            | NodeData::Line { appearance, .. }
+            | NodeData::Star { appearance, .. }
+            | NodeData::RegularPolygon { appearance, .. }
+            | NodeData::Connector { appearance, .. }
+            | NodeData::ShapeWithText { appearance, .. }
+            | NodeData::Component { appearance, .. }
+            | NodeData::ComponentSet { appearance, .. }
+            | NodeData::Instance { appearance, .. }
+            | NodeData::BooleanOperation { appearance, .. }
+            | NodeData::Table { appearance, .. }
+            | NodeData::TableCell { appearance, .. } => Some(appearance),
+            _ => None,
+        }
+    }
+
+    /// Fill paints; on text nodes these give the text colour
+    pub fn fills(&self) -> Option<&[Paint]> {
+        match &self.data {
+            NodeData::Section { fills, .. }
+            | NodeData::Frame { fills, .. }
+            | NodeData::Text { fills, .. }
+            | NodeData::Rectangle { fills, .. }
+            | NodeData::Vector { fills, .. }
+            | NodeData::Ellipse { fills, .. }
+            | NodeData::Line { fills, .. }
+            | NodeData::Star { fills, .. }
+            | NodeData::RegularPolygon { fills, .. }
+            | NodeData::ShapeWithText { fills, .. }
+            | NodeData::Sticky { fills, .. }
+            | NodeData::BooleanOperation { fills, .. }
+            | NodeData::Table { fills, .. }
+            | NodeData::TableCell { fills, .. } => Some(fills),
+            _ => None,
+        }
+    }
+
     pub fn style(&self) -> Option<&TypeStyle> {
         match &self.data {
             NodeData::Text { style, .. } => style.as_ref(),
@@ -308,17 +474,20 @@ impl Serialize for Node {
             }
             NodeData::Section {
                 absolute_bounding_box,
+                absolute_render_bounds,
                 fills,
                 strokes,
                 stroke_weight,
                 stroke_align,
                 section_contents_hidden,
                 children,
-                ..
             } => {
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
+                if let Some(bounds) = absolute_render_bounds {
+                    map.serialize_entry("absoluteRenderBounds", bounds)?;
+                }
                 map.serialize_entry("fills", fills)?;
                 map.serialize_entry("strokes", strokes)?;
                 map.serialize_entry("strokeWeight", stroke_weight)?;
@@ -330,6 +499,8 @@ impl Serialize for Node {
                 absolute_bounding_box,
                 fills,
                 clips_content,
+                layout,
+                appearance,
                 children,
             } => {
                 if let Some(bb) = absolute_bounding_box {
@@ -337,62 +508,150 @@ impl Serialize for Node {
                 }
                 map.serialize_entry("fills", fills)?;
                 map.serialize_entry("clipsContent", clips_content)?;
+                layout.serialize_entries(&mut map)?;
+                appearance.serialize_entries(&mut map)?;
                 map.serialize_entry("children", children)?;
             }
             NodeData::Group {
                 absolute_bounding_box,
+                appearance,
                 children,
             } => {
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
+                appearance.serialize_entries(&mut map)?;
                 map.serialize_entry("children", children)?;
             }
             NodeData::Text {
                 characters,
                 absolute_bounding_box,
+                fills,
                 style,
+                character_style_overrides,
+                style_override_table,
+                appearance,
             } => {
                 map.serialize_entry("characters", characters)?;
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
+                map.serialize_entry("fills", fills)?;
                 if let Some(s) = style {
                     map.serialize_entry("style", s)?;
                 }
+                if !character_style_overrides.is_empty() {
+                    map.serialize_entry("characterStyleOverrides", character_style_overrides)?;
+                    map.serialize_entry("styleOverrideTable", style_override_table)?;
+                }
+                appearance.serialize_entries(&mut map)?;
             }
             NodeData::Rectangle {
                 absolute_bounding_box,
                 corner_radius,
                 fills,
+                appearance,
             } => {
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
                 map.serialize_entry("cornerRadius", corner_radius)?;
                 map.serialize_entry("fills", fills)?;
+                appearance.serialize_entries(&mut map)?;
             }
             NodeData::Vector {
                 absolute_bounding_box,
                 fills,
+                appearance,
+            }
+            | NodeData::Ellipse {
+                absolute_bounding_box,
+                fills,
+                appearance,
+            }
+            | NodeData::Line {
+                absolute_bounding_box,
+                fills,
+                appearance,
+            }
+            | NodeData::Star {
+                absolute_bounding_box,
+                fills,
+                appearance,
+            }
+            | NodeData::RegularPolygon {
+                absolute_bounding_box,
+                fills,
+                appearance,
+            } => {
+                if let Some(bb) = absolute_bounding_box {
+                    map.serialize_entry("absoluteBoundingBox", bb)?;
+                }
+                map.serialize_entry("fills", fills)?;
+                appearance.serialize_entries(&mut map)?;
+            }
+            NodeData::Slice {
+                absolute_bounding_box,
+            }
+            | NodeData::Stamp {
+                absolute_bounding_box,
+            }
+            | NodeData::Embed {
+                absolute_bounding_box,
+            } => {
+                if let Some(bb) = absolute_bounding_box {
+                    map.serialize_entry("absoluteBoundingBox", bb)?;
+                }
             }
-            | NodeData::Shape {
+            NodeData::Connector {
+                characters,
+                absolute_bounding_box,
+                appearance,
+            } => {
+                map.serialize_entry("characters", characters)?;
+                if let Some(bb) = absolute_bounding_box {
+                    map.serialize_entry("absoluteBoundingBox", bb)?;
+                }
+                appearance.serialize_entries(&mut map)?;
+            }
+            NodeData::ShapeWithText {
+                characters,
+                shape_type,
                 absolute_bounding_box,
                 fills,
+                appearance,
             } => {
+                map.serialize_entry("characters", characters)?;
+                if let Some(shape_type) = shape_type {
+                    map.serialize_entry("shapeType", shape_type)?;
+                }
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
                 map.serialize_entry("fills", fills)?;
+                appearance.serialize_entries(&mut map)?;
+            }
+            NodeData::Widget {
+                absolute_bounding_box,
+                children,
+            } => {
+                if let Some(bb) = absolute_bounding_box {
+                    map.serialize_entry("absoluteBoundingBox", bb)?;
+                }
+                map.serialize_entry("children", children)?;
             }
             NodeData::Component {
                 component_key,
                 absolute_bounding_box,
+                layout,
+                appearance,
                 children,
             }
             | NodeData::ComponentSet {
                 component_key,
                 absolute_bounding_box,
+                layout,
+                appearance,
                 children,
             } => {
                 if let Some(key) = component_key {
@@ -401,17 +660,23 @@ impl Serialize for Node {
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
+                layout.serialize_entries(&mut map)?;
+                appearance.serialize_entries(&mut map)?;
                 map.serialize_entry("children", children)?;
             }
             NodeData::Instance {
                 component_id,
                 absolute_bounding_box,
+                layout,
+                appearance,
                 children,
             } => {
                 map.serialize_entry("componentId", component_id)?;
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
+                layout.serialize_entries(&mut map)?;
+                appearance.serialize_entries(&mut map)?;
                 map.serialize_entry("children", children)?;
             }
             NodeData::Sticky {
@@ -428,22 +693,26 @@ impl Serialize for Node {
             NodeData::BooleanOperation {
                 absolute_bounding_box,
                 fills,
+                appearance,
                 children,
             }
             | NodeData::Table {
                 absolute_bounding_box,
                 fills,
+                appearance,
                 children,
             }
             | NodeData::TableCell {
                 absolute_bounding_box,
                 fills,
+                appearance,
                 children,
             } => {
                 if let Some(bb) = absolute_bounding_box {
                     map.serialize_entry("absoluteBoundingBox", bb)?;
                 }
                 map.serialize_entry("fills", fills)?;
+                appearance.serialize_entries(&mut map)?;
                 map.serialize_entry("children", children)?;
             }
             NodeData::Other {
@@ -457,6 +726,10 @@ impl Serialize for Node {
             }
         }
 
+        for (key, value) in &self.extra {
+            map.serialize_entry(key, value)?;
+        }
+
         map.end()
     }
 }
@@ -466,157 +739,306 @@ impl<'de> Deserialize<'de> for Node {
     where
         D: Deserializer<'de>,
     {
-        let value = Value::deserialize(deserializer)?;
-
-        let base: NodeBase =
-            serde_json::from_value(value.clone()).map_err(serde::de::Error::custom)?;
-
-        let data = match base.node_type.as_str() {
-            "CANVAS" => {
-                let d: CanvasRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Canvas {
-                    background_color: d.background_color,
-                    export_settings: d.export_settings,
-                    children: d.children,
-                }
+        deserializer.deserialize_map(NodeMapVisitor)
+    }
+}
+
+/// Streams `children` straight into nodes and buffers only a node's own fields,
+/// so deserialization stays linear in the size of the tree.
+struct NodeMapVisitor;
+
+impl<'de> serde::de::Visitor<'de> for NodeMapVisitor {
+    type Value = Node;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a Figma node object")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Node, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut fields = Map::new();
+        let mut children = Vec::new();
+
+        while let Some(key) = access.next_key::<String>()? {
+            if key == "children" {
+                children = access
+                    .next_value::<Option<Vec<Node>>>()?
+                    .unwrap_or_default();
+            } else {
+                fields.insert(key, access.next_value()?);
             }
-            "SECTION" => {
-                let d: SectionRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Section {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    absolute_render_bounds: d.absolute_render_bounds,
-                    fills: d.fills,
-                    strokes: d.strokes,
-                    stroke_weight: d.stroke_weight,
-                    stroke_align: d.stroke_align,
-                    section_contents_hidden: d.section_contents_hidden,
-                    children: d.children,
-                }
+        }
+
+        build_node(fields, children)
+    }
+}
+
+/// Dispatch on the node's `type` to the matching raw helper
+fn build_node<E: serde::de::Error>(
+    mut fields: Map<String, Value>,
+    children: Vec<Node>,
+) -> Result<Node, E> {
+    /// Split a node into its modeled parts; `L` and `A` are `()` for types without them
+    fn raw<T, L, A, E>(value: Value, extra: &mut Map<String, Value>) -> Result<(T, L, A), E>
+    where
+        T: serde::de::DeserializeOwned,
+        L: serde::de::DeserializeOwned,
+        A: serde::de::DeserializeOwned,
+        E: serde::de::Error,
+    {
+        let raw: RawNode<T, L, A> = serde_json::from_value(value).map_err(E::custom)?;
+        *extra = raw.extra;
+        Ok((raw.fields, raw.layout, raw.appearance))
+    }
+
+    let base_fields: Map<String, Value> = ["type", "id", "name", "visible", "locked"]
+        .into_iter()
+        .filter_map(|key| fields.shift_remove_entry(key))
+        .collect();
+    let base = NodeBase::deserialize(Value::Object(base_fields)).map_err(E::custom)?;
+    let value = Value::Object(fields);
+    let mut extra = Map::new();
+
+    let data = match base.node_type.as_str() {
+        "CANVAS" => {
+            let (d, (), ()): (CanvasRaw, _, _) = raw(value, &mut extra)?;
+            NodeData::Canvas {
+                background_color: d.background_color,
+                export_settings: d.export_settings,
+                children,
             }
-            "FRAME" => {
-                let d: FrameRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Frame {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    fills: d.fills,
-                    clips_content: d.clips_content,
-                    children: d.children,
-                }
+        }
+        "SECTION" => {
+            let (d, (), ()): (SectionRaw, _, _) = raw(value, &mut extra)?;
+            NodeData::Section {
+                absolute_bounding_box: d.absolute_bounding_box,
+                absolute_render_bounds: d.absolute_render_bounds,
+                fills: d.fills,
+                strokes: d.strokes,
+                stroke_weight: d.stroke_weight,
+                stroke_align: d.stroke_align,
+                section_contents_hidden: d.section_contents_hidden,
+                children,
             }
-            "GROUP" => {
-                let d: GroupRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Group {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    children: d.children,
-                }
+        }
+        "FRAME" => {
+            let (d, layout, appearance): (FrameRaw, Layout, Appearance) = raw(value, &mut extra)?;
+            NodeData::Frame {
+                absolute_bounding_box: d.absolute_bounding_box,
+                fills: d.fills,
+                clips_content: d.clips_content,
+                layout,
+                appearance,
+                children,
             }
-            "TEXT" => {
-                let d: TextRaw = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Text {
-                    characters: d.characters,
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    style: d.style,
-                }
+        }
+        "GROUP" => {
+            let (d, (), appearance): (GroupRaw, _, Appearance) = raw(value, &mut extra)?;
+            NodeData::Group {
+                absolute_bounding_box: d.absolute_bounding_box,
+                appearance,
+                children,
             }
-            "RECTANGLE" => {
-                let d: RectangleRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Rectangle {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    corner_radius: d.corner_radius,
-                    fills: d.fills,
-                }
+        }
+        "TEXT" => {
+            let (d, (), appearance): (TextRaw, _, Appearance) = raw(value, &mut extra)?;
+            NodeData::Text {
+                characters: d.characters,
+                absolute_bounding_box: d.absolute_bounding_box,
+                fills: d.fills,
+                style: d.style,
+                character_style_overrides: d.character_style_overrides,
+                style_override_table: d.style_override_table,
+                appearance,
             }
-            "VECTOR" | "ELLIPSE" | "LINE" | "REGULAR_POLYGON" | "STAR" | "SHAPE_WITH_TEXT"
-            | "CONNECTOR" | "WIDGET" => {
-                let d: ShapeRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Shape {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    fills: d.fills,
-                }
+        }
+        "RECTANGLE" => {
+            let (d, (), appearance): (RectangleRaw, _, Appearance) = raw(value, &mut extra)?;
+            NodeData::Rectangle {
+                absolute_bounding_box: d.absolute_bounding_box,
+                corner_radius: d.corner_radius,
+                fills: d.fills,
+                appearance,
             }
-            "COMPONENT" => {
-                let d: ComponentRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Component {
-                    component_key: d.component_key,
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    children: d.children,
-                }
+        }
+        "VECTOR" | "ELLIPSE" | "LINE" | "STAR" | "REGULAR_POLYGON" => {
+            let (
+                ShapeRaw {
+                    absolute_bounding_box,
+                    fills,
+                },
+                (),
+                appearance,
+            ): (_, _, Appearance) = raw(value, &mut extra)?;
+            match base.node_type.as_str() {
+                "VECTOR" => NodeData::Vector {
+                    absolute_bounding_box,
+                    fills,
+                    appearance,
+                },
+                "ELLIPSE" => NodeData::Ellipse {
+                    absolute_bounding_box,
+                    fills,
+                    appearance,
+                },
+                "LINE" => NodeData::Line {
+                    absolute_bounding_box,
+                    fills,
+                    appearance,
+                },
+                "STAR" => NodeData::Star {
+                    absolute_bounding_box,
+                    fills,
+                    appearance,
+                },
+                _ => NodeData::RegularPolygon {
+                    absolute_bounding_box,
+                    fills,
+                    appearance,
+                },
             }
-            "COMPONENT_SET" => {
-                let d: ComponentRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::ComponentSet {
-                    component_key: d.component_key,
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    children: d.children,
-                }
+        }
+        "SLICE" => NodeData::Slice {
+            absolute_bounding_box: raw::<BoundsRaw, (), (), _>(value, &mut extra)?
+                .0
+                .absolute_bounding_box,
+        },
+        "STAMP" => NodeData::Stamp {
+            absolute_bounding_box: raw::<BoundsRaw, (), (), _>(value, &mut extra)?
+                .0
+                .absolute_bounding_box,
+        },
+        "EMBED" => NodeData::Embed {
+            absolute_bounding_box: raw::<BoundsRaw, (), (), _>(value, &mut extra)?
+                .0
+                .absolute_bounding_box,
+        },
+        "WIDGET" => NodeData::Widget {
+            absolute_bounding_box: raw::<BoundsRaw, (), (), _>(value, &mut extra)?
+                .0
+                .absolute_bounding_box,
+            children,
+        },
+        "CONNECTOR" => {
+            let (d, (), appearance): (ConnectorRaw, _, Appearance) = raw(value, &mut extra)?;
+            NodeData::Connector {
+                characters: d.characters,
+                absolute_bounding_box: d.absolute_bounding_box,
+                appearance,
             }
-            "INSTANCE" => {
-                let d: InstanceRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Instance {
-                    component_id: d.component_id,
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    children: d.children,
-                }
+        }
+        "SHAPE_WITH_TEXT" => {
+            let (d, (), appearance): (ShapeWithTextRaw, _, Appearance) = raw(value, &mut extra)?;
+            NodeData::ShapeWithText {
+                characters: d.characters,
+                shape_type: d.shape_type,
+                absolute_bounding_box: d.absolute_bounding_box,
+                fills: d.fills,
+                appearance,
             }
-            "STICKY" => {
-                let d: StickyRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Sticky {
-                    characters: d.characters,
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    fills: d.fills,
-                }
+        }
+        "COMPONENT" => {
+            let (d, layout, appearance): (ComponentRaw, Layout, Appearance) =
+                raw(value, &mut extra)?;
+            NodeData::Component {
+                component_key: d.component_key,
+                absolute_bounding_box: d.absolute_bounding_box,
+                layout,
+                appearance,
+                children,
             }
-            "BOOLEAN_OPERATION" => {
-                let d: ContainerWithFillsRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::BooleanOperation {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    fills: d.fills,
-                    children: d.children,
-                }
+        }
+        "COMPONENT_SET" => {
+            let (d, layout, appearance): (ComponentRaw, Layout, Appearance) =
+                raw(value, &mut extra)?;
+            NodeData::ComponentSet {
+                component_key: d.component_key,
+                absolute_bounding_box: d.absolute_bounding_box,
+                layout,
+                appearance,
+                children,
             }
-            "TABLE" => {
-                let d: ContainerWithFillsRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Table {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    fills: d.fills,
-                    children: d.children,
-                }
+        }
+        "INSTANCE" => {
+            let (d, layout, appearance): (InstanceRaw, Layout, Appearance) =
+                raw(value, &mut extra)?;
+            NodeData::Instance {
+                component_id: d.component_id,
+                absolute_bounding_box: d.absolute_bounding_box,
+                layout,
+                appearance,
+                children,
             }
-            "TABLE_CELL" => {
-                let d: ContainerWithFillsRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::TableCell {
-                    absolute_bounding_box: d.absolute_bounding_box,
-                    fills: d.fills,
-                    children: d.children,
-                }
+        }
+        "STICKY" => {
+            let (d, (), ()): (StickyRaw, _, _) = raw(value, &mut extra)?;
+            NodeData::Sticky {
+                characters: d.characters,
+                absolute_bounding_box: d.absolute_bounding_box,
+                fills: d.fills,
             }
-            _ => {
-                let d: OtherRaw =
-                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                NodeData::Other {
-                    characters: d.characters,
-                    children: d.children,
-                }
+        }
+        "BOOLEAN_OPERATION" => {
+            let (d, (), appearance): (ContainerWithFillsRaw, _, Appearance) =
+                raw(value, &mut extra)?;
+            NodeData::BooleanOperation {
+                absolute_bounding_box: d.absolute_bounding_box,
+                fills: d.fills,
+                appearance,
+                children,
             }
-        };
+        }
+        "TABLE" => {
+            let (d, (), appearance): (ContainerWithFillsRaw, _, Appearance) =
+                raw(value, &mut extra)?;
+            NodeData::Table {
+                absolute_bounding_box: d.absolute_bounding_box,
+                fills: d.fills,
+                appearance,
+                children,
+            }
+        }
+        "TABLE_CELL" => {
+            let (d, (), appearance): (ContainerWithFillsRaw, _, Appearance) =
+                raw(value, &mut extra)?;
+            NodeData::TableCell {
+                absolute_bounding_box: d.absolute_bounding_box,
+                fills: d.fills,
+                appearance,
+                children,
+            }
+        }
+        _ => {
+            let (d, (), ()): (OtherRaw, _, _) = raw(value, &mut extra)?;
+            NodeData::Other {
+                characters: d.characters,
+                children,
+            }
+        }
+    };
 
-        Ok(Node { base, data })
-    }
+    Ok(Node { base, data, extra })
 }
 
 // Raw deserialization helpers (internal use only)
+/// Typed parts of a node plus whatever is left over
+///
+/// The parts must not use `flatten` themselves; serde only removes entries
+/// consumed by flattened structs, so anything else would also land in `extra`.
+#[derive(Deserialize)]
+struct RawNode<T, L, A> {
+    #[serde(flatten)]
+    fields: T,
+    #[serde(flatten)]
+    layout: L,
+    #[serde(flatten)]
+    appearance: A,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CanvasRaw {
@@ -624,8 +1046,6 @@ struct CanvasRaw {
     background_color: Option<Color>,
     #[serde(rename = "exportSettings", default)]
     export_settings: Vec<ExportSetting>,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Deserialize)]
@@ -645,8 +1065,6 @@ struct SectionRaw {
     stroke_align: String,
     #[serde(rename = "sectionContentsHidden", default)]
     section_contents_hidden: bool,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Deserialize)]
@@ -658,8 +1076,6 @@ struct FrameRaw {
     fills: Vec<Paint>,
     #[serde(rename = "clipsContent", default)]
     clips_content: bool,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Deserialize)]
@@ -667,8 +1083,6 @@ struct FrameRaw {
 struct GroupRaw {
     #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
     absolute_bounding_box: Option<BoundingBox>,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Deserialize)]
@@ -678,8 +1092,14 @@ struct TextRaw {
     characters: String,
     #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
     absolute_bounding_box: Option<BoundingBox>,
+    #[serde(default)]
+    fills: Vec<Paint>,
     #[serde(default, with = "option_struct")]
     style: Option<TypeStyle>,
+    #[serde(default)]
+    character_style_overrides: Vec<usize>,
+    #[serde(default)]
+    style_override_table: HashMap<String, TypeStyle>,
 }
 
 #[derive(Deserialize)]
@@ -702,6 +1122,35 @@ struct ShapeRaw {
     fills: Vec<Paint>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConnectorRaw {
+    #[serde(default)]
+    characters: String,
+    #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
+    absolute_bounding_box: Option<BoundingBox>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShapeWithTextRaw {
+    #[serde(default)]
+    characters: String,
+    #[serde(default)]
+    shape_type: Option<String>,
+    #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
+    absolute_bounding_box: Option<BoundingBox>,
+    #[serde(default)]
+    fills: Vec<Paint>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BoundsRaw {
+    #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
+    absolute_bounding_box: Option<BoundingBox>,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ComponentRaw {
@@ -709,8 +1158,6 @@ struct ComponentRaw {
     component_key: Option<String>,
     #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
     absolute_bounding_box: Option<BoundingBox>,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Deserialize)]
@@ -720,8 +1167,6 @@ struct InstanceRaw {
     component_id: String,
     #[serde(rename = "absoluteBoundingBox", default, with = "option_struct")]
     absolute_bounding_box: Option<BoundingBox>,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Deserialize)]
@@ -742,8 +1187,6 @@ struct ContainerWithFillsRaw {
     absolute_bounding_box: Option<BoundingBox>,
     #[serde(default)]
     fills: Vec<Paint>,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Deserialize)]
@@ -751,8 +1194,6 @@ struct ContainerWithFillsRaw {
 struct OtherRaw {
     #[serde(default)]
     characters: Option<String>,
-    #[serde(default)]
-    children: Vec<Node>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
@@ -772,6 +1213,221 @@ pub struct Color {
     pub a: f64,
 }
 
+/// Auto-layout, constraint and layout grid properties
+///
+/// Only fields present in the API response are kept, so frames without
+/// auto-layout serialize exactly as before.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Layout {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_wrap: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_axis_sizing_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_axis_sizing_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_axis_align_items: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_axis_align_items: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_axis_align_content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_spacing: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_axis_spacing: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_left: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_right: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_top: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_bottom: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_reverse_z_index: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strokes_included_in_layout: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_sizing_horizontal: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_sizing_vertical: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_align: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_grow: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_positioning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_width: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_height: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_height: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<LayoutConstraint>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layout_grids: Vec<LayoutGrid>,
+}
+
+impl Layout {
+    /// Write present fields into a node's map, mirroring the API field names
+    fn serialize_entries<M: serde::ser::SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        macro_rules! entry {
+            ($key:literal, $field:expr) => {
+                if let Some(value) = &$field {
+                    map.serialize_entry($key, value)?;
+                }
+            };
+        }
+
+        entry!("layoutMode", self.layout_mode);
+        entry!("layoutWrap", self.layout_wrap);
+        entry!("primaryAxisSizingMode", self.primary_axis_sizing_mode);
+        entry!("counterAxisSizingMode", self.counter_axis_sizing_mode);
+        entry!("primaryAxisAlignItems", self.primary_axis_align_items);
+        entry!("counterAxisAlignItems", self.counter_axis_align_items);
+        entry!("counterAxisAlignContent", self.counter_axis_align_content);
+        entry!("itemSpacing", self.item_spacing);
+        entry!("counterAxisSpacing", self.counter_axis_spacing);
+        entry!("paddingLeft", self.padding_left);
+        entry!("paddingRight", self.padding_right);
+        entry!("paddingTop", self.padding_top);
+        entry!("paddingBottom", self.padding_bottom);
+        entry!("itemReverseZIndex", self.item_reverse_z_index);
+        entry!("strokesIncludedInLayout", self.strokes_included_in_layout);
+        entry!("layoutSizingHorizontal", self.layout_sizing_horizontal);
+        entry!("layoutSizingVertical", self.layout_sizing_vertical);
+        entry!("layoutAlign", self.layout_align);
+        entry!("layoutGrow", self.layout_grow);
+        entry!("layoutPositioning", self.layout_positioning);
+        entry!("minWidth", self.min_width);
+        entry!("maxWidth", self.max_width);
+        entry!("minHeight", self.min_height);
+        entry!("maxHeight", self.max_height);
+        entry!("constraints", self.constraints);
+        if !self.layout_grids.is_empty() {
+            map.serialize_entry("layoutGrids", &self.layout_grids)?;
+        }
+        Ok(())
+    }
+}
+
+/// Stroke, corner and effect properties shared by visible node types
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Appearance {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strokes: Vec<Paint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_align: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stroke_dashes: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rectangle_corner_radii: Option<[f64; 4]>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effects: Vec<Effect>,
+}
+
+impl Appearance {
+    /// Write present fields into a node's map, mirroring the API field names
+    fn serialize_entries<M: serde::ser::SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        if !self.strokes.is_empty() {
+            map.serialize_entry("strokes", &self.strokes)?;
+        }
+        if let Some(weight) = self.stroke_weight {
+            map.serialize_entry("strokeWeight", &weight)?;
+        }
+        if let Some(align) = &self.stroke_align {
+            map.serialize_entry("strokeAlign", align)?;
+        }
+        if !self.stroke_dashes.is_empty() {
+            map.serialize_entry("strokeDashes", &self.stroke_dashes)?;
+        }
+        if let Some(radii) = &self.rectangle_corner_radii {
+            map.serialize_entry("rectangleCornerRadii", radii)?;
+        }
+        if !self.effects.is_empty() {
+            map.serialize_entry("effects", &self.effects)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shadow or blur effect
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Effect {
+    #[serde(rename = "type")]
+    pub effect_type: String,
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    #[serde(default)]
+    pub radius: f64,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "option_struct"
+    )]
+    pub color: Option<Color>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "option_struct"
+    )]
+    pub offset: Option<Vector2>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spread: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_shadow_behind_node: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct Vector2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct ColorStop {
+    pub position: f64,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LayoutConstraint {
+    pub vertical: String,
+    pub horizontal: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutGrid {
+    pub pattern: String,
+    #[serde(default)]
+    pub section_size: f64,
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alignment: Option<String>,
+    #[serde(default)]
+    pub gutter_size: f64,
+    #[serde(default)]
+    pub offset: f64,
+    #[serde(default)]
+    pub count: i32,
+}
+
 mod option_struct {
     use serde::de::DeserializeOwned;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -799,7 +1455,7 @@ mod option_struct {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Paint {
     #[serde(rename = "type", default)]
@@ -814,13 +1470,24 @@ pub struct Paint {
     pub opacity: f64,
     #[serde(rename = "blendMode", default)]
     pub blend_mode: String,
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gradient_handle_positions: Vec<Vector2>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gradient_stops: Vec<ColorStop>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale_mode: Option<String>,
 }
 
 const fn default_opacity() -> f64 {
     1.0
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Text style; override table entries only carry the fields they change
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TypeStyle {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -829,6 +1496,59 @@ pub struct TypeStyle {
     pub font_size: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub font_weight: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_height_px: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_height_percent: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_height_percent_font_size: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_height_unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub letter_spacing: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_align_horizontal: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_align_vertical: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_case: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_decoration: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paragraph_spacing: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paragraph_indent: Option<f64>,
+}
+
+impl TypeStyle {
+    /// Apply an override on top of this style, keeping fields the override leaves unset
+    pub fn merged(&self, other: &Self) -> Self {
+        macro_rules! pick {
+            ($($field:ident),*) => {
+                Self { $($field: other.$field.clone().or_else(|| self.$field.clone()),)* }
+            };
+        }
+
+        pick!(
+            font_family,
+            font_size,
+            font_weight,
+            italic,
+            line_height_px,
+            line_height_percent,
+            line_height_percent_font_size,
+            line_height_unit,
+            letter_spacing,
+            text_align_horizontal,
+            text_align_vertical,
+            text_case,
+            text_decoration,
+            paragraph_spacing,
+            paragraph_indent
+        )
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -879,3 +1599,283 @@ pub struct Style {
     #[serde(default)]
     pub style_type: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_auto_layout_round_trip() {
+        let raw = json!({
+            "type": "FRAME",
+            "id": "1:1",
+            "name": "Card",
+            "layoutMode": "VERTICAL",
+            "primaryAxisAlignItems": "SPACE_BETWEEN",
+            "counterAxisAlignItems": "CENTER",
+            "itemSpacing": 12.0,
+            "counterAxisSpacing": 8.0,
+            "paddingLeft": 16.0,
+            "paddingRight": 16.0,
+            "paddingTop": 24.0,
+            "paddingBottom": 24.0,
+            "layoutSizingHorizontal": "FILL",
+            "layoutSizingVertical": "HUG",
+            "constraints": { "vertical": "TOP", "horizontal": "LEFT_RIGHT" },
+            "layoutGrids": [{
+                "pattern": "COLUMNS",
+                "sectionSize": 64.0,
+                "visible": true,
+                "alignment": "STRETCH",
+                "gutterSize": 16.0,
+                "offset": 0.0,
+                "count": 4
+            }],
+            "children": []
+        });
+
+        let node: Node = serde_json::from_value(raw).unwrap();
+        let layout = node.layout().unwrap();
+        assert_eq!(layout.layout_mode.as_deref(), Some("VERTICAL"));
+        assert_eq!(layout.item_spacing, Some(12.0));
+        assert_eq!(layout.layout_sizing_vertical.as_deref(), Some("HUG"));
+        assert_eq!(layout.layout_grids[0].count, 4);
+
+        let value = serde_json::to_value(&node).unwrap();
+        assert_eq!(value["primaryAxisAlignItems"], "SPACE_BETWEEN");
+        assert_eq!(value["paddingTop"], 24.0);
+        assert_eq!(value["counterAxisSpacing"], 8.0);
+        assert_eq!(value["constraints"]["horizontal"], "LEFT_RIGHT");
+        assert_eq!(value["layoutGrids"][0]["gutterSize"], 16.0);
+
+        let again: Node = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            again.layout().unwrap().constraints,
+            layout.constraints.clone()
+        );
+    }
+
+    #[test]
+    fn test_instance_layout_and_absent_fields() {
+        let instance: Node = serde_json::from_value(json!({
+            "type": "INSTANCE",
+            "id": "2:1",
+            "name": "Button",
+            "componentId": "9:1",
+            "layoutMode": "HORIZONTAL",
+            "layoutGrow": 1.0,
+            "children": []
+        }))
+        .unwrap();
+        assert_eq!(instance.layout().unwrap().layout_grow, Some(1.0));
+
+        let plain: Node = serde_json::from_value(json!({
+            "type": "FRAME",
+            "id": "3:1",
+            "name": "Plain",
+            "children": []
+        }))
+        .unwrap();
+        let value = serde_json::to_value(&plain).unwrap();
+        assert!(value.get("layoutMode").is_none());
+        assert!(value.get("layoutGrids").is_none());
+    }
+
+    #[test]
+    fn test_effects_strokes_and_gradients_round_trip() {
+        let raw = json!({
+            "type": "FRAME",
+            "id": "4:1",
+            "name": "Card",
+            "layoutMode": "HORIZONTAL",
+            "fills": [{
+                "type": "GRADIENT_LINEAR",
+                "blendMode": "NORMAL",
+                "gradientHandlePositions": [{ "x": 0.0, "y": 0.0 }, { "x": 1.0, "y": 1.0 }],
+                "gradientStops": [
+                    { "position": 0.0, "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } },
+                    { "position": 1.0, "color": { "r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0 } }
+                ]
+            }, {
+                "type": "IMAGE",
+                "visible": false,
+                "imageRef": "abc123",
+                "scaleMode": "FILL"
+            }],
+            "strokes": [{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.1 } }],
+            "strokeWeight": 1.0,
+            "strokeAlign": "INSIDE",
+            "strokeDashes": [4.0, 2.0],
+            "rectangleCornerRadii": [8.0, 8.0, 0.0, 0.0],
+            "effects": [{
+                "type": "DROP_SHADOW",
+                "visible": true,
+                "radius": 12.0,
+                "spread": 2.0,
+                "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.25 },
+                "offset": { "x": 0.0, "y": 4.0 },
+                "blendMode": "NORMAL"
+            }, {
+                "type": "BACKGROUND_BLUR",
+                "radius": 20.0
+            }],
+            "children": []
+        });
+
+        let node: Node = serde_json::from_value(raw).unwrap();
+        let appearance = node.appearance().unwrap();
+        assert_eq!(appearance.stroke_align.as_deref(), Some("INSIDE"));
+        assert_eq!(
+            appearance.rectangle_corner_radii,
+            Some([8.0, 8.0, 0.0, 0.0])
+        );
+        assert_eq!(appearance.effects.len(), 2);
+        assert_eq!(
+            appearance.effects[0].offset,
+            Some(Vector2 { x: 0.0, y: 4.0 })
+        );
+        assert!(appearance.effects[1].visible);
+        assert_eq!(
+            node.layout().unwrap().layout_mode.as_deref(),
+            Some("HORIZONTAL")
+        );
+
+        let value = serde_json::to_value(&node).unwrap();
+        assert_eq!(value["fills"][0]["gradientStops"][1]["color"]["b"], 1.0);
+        assert_eq!(value["fills"][0]["gradientHandlePositions"][1]["x"], 1.0);
+        assert_eq!(value["fills"][1]["imageRef"], "abc123");
+        assert_eq!(value["fills"][1]["visible"], false);
+        assert_eq!(value["strokeDashes"], json!([4.0, 2.0]));
+        assert_eq!(value["effects"][0]["spread"], 2.0);
+        assert_eq!(value["effects"][1]["type"], "BACKGROUND_BLUR");
+
+        let again: Node = serde_json::from_value(value).unwrap();
+        assert_eq!(again.appearance().unwrap().effects[0].radius, 12.0);
+    }
+
+    #[test]
+    fn test_shape_strokes() {
+        let node: Node = serde_json::from_value(json!({
+            "type": "ELLIPSE",
+            "id": "5:1",
+            "name": "Dot",
+            "strokes": [{ "type": "SOLID", "color": { "r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0 } }],
+            "strokeWeight": 2.0
+        }))
+        .unwrap();
+
+        let appearance = node.appearance().unwrap();
+        assert_eq!(appearance.strokes.len(), 1);
+        assert_eq!(appearance.stroke_weight, Some(2.0));
+        assert!(appearance.effects.is_empty());
+    }
+
+    #[test]
+    fn test_dispatch_on_node_type() {
+        let shape = |node_type: &str| -> Node {
+            serde_json::from_value(json!({
+                "type": node_type,
+                "id": "6:1",
+                "name": node_type,
+                "fills": []
+            }))
+            .unwrap()
+        };
+
+        assert!(matches!(shape("VECTOR").data, NodeData::Vector { .. }));
+        assert!(matches!(shape("ELLIPSE").data, NodeData::Ellipse { .. }));
+        assert!(matches!(shape("LINE").data, NodeData::Line { .. }));
+        assert!(matches!(shape("STAR").data, NodeData::Star { .. }));
+        assert!(matches!(
+            shape("REGULAR_POLYGON").data,
+            NodeData::RegularPolygon { .. }
+        ));
+        assert!(matches!(shape("SLICE").data, NodeData::Slice { .. }));
+        assert!(matches!(shape("STAMP").data, NodeData::Stamp { .. }));
+        assert!(matches!(shape("EMBED").data, NodeData::Embed { .. }));
+        assert!(matches!(shape("WIDGET").data, NodeData::Widget { .. }));
+        assert!(matches!(shape("WASHI_TAPE").data, NodeData::Other { .. }));
+
+        // Same shape as a frame, but must stay a group
+        let group = shape("GROUP");
+        assert!(matches!(group.data, NodeData::Group { .. }));
+        assert_eq!(serde_json::to_value(&group).unwrap()["type"], "GROUP");
+    }
+
+    #[test]
+    fn test_figjam_text_shapes() {
+        let shape: Node = serde_json::from_value(json!({
+            "type": "SHAPE_WITH_TEXT",
+            "id": "7:1",
+            "name": "Decision",
+            "shapeType": "DIAMOND",
+            "characters": "Logged in?"
+        }))
+        .unwrap();
+        assert_eq!(shape.characters(), Some("Logged in?"));
+
+        let connector: Node = serde_json::from_value(json!({
+            "type": "CONNECTOR",
+            "id": "7:2",
+            "name": "Arrow",
+            "characters": "yes",
+            "strokeWeight": 2.0,
+            "fills": [{ "type": "SOLID", "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } }]
+        }))
+        .unwrap();
+        assert_eq!(connector.characters(), Some("yes"));
+        assert_eq!(connector.appearance().unwrap().stroke_weight, Some(2.0));
+
+        // Connectors model no fills of their own, so they pass through untouched
+        let value = serde_json::to_value(&connector).unwrap();
+        assert_eq!(value["fills"][0]["type"], "SOLID");
+        assert_eq!(value["fills"][0]["color"]["r"], 1.0);
+        let reparsed: Node = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&reparsed).unwrap(), value);
+
+        let value = serde_json::to_value(&shape).unwrap();
+        assert_eq!(value["shapeType"], "DIAMOND");
+    }
+
+    #[test]
+    fn test_unmodeled_fields_pass_through() {
+        let raw = json!({
+            "type": "INSTANCE",
+            "id": "8:1",
+            "name": "Button",
+            "componentId": "9:1",
+            "layoutMode": "HORIZONTAL",
+            "componentProperties": {
+                "Label#1:0": { "type": "TEXT", "value": "Buy" }
+            },
+            "boundVariables": { "itemSpacing": { "type": "VARIABLE_ALIAS", "id": "VariableID:1" } },
+            "devStatus": { "type": "READY_FOR_DEV" },
+            "exportSettings": [{ "suffix": "", "format": "PNG", "constraint": { "type": "SCALE", "value": 2 } }],
+            "pluginData": { "tokens": { "count": 3, "ratio": 0.5 } },
+            "children": []
+        });
+
+        let node: Node = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(
+            node.layout().unwrap().layout_mode.as_deref(),
+            Some("HORIZONTAL")
+        );
+        assert_eq!(
+            node.extra.keys().collect::<Vec<_>>(),
+            vec![
+                "componentProperties",
+                "boundVariables",
+                "devStatus",
+                "exportSettings",
+                "pluginData"
+            ]
+        );
+
+        let value = serde_json::to_value(&node).unwrap();
+        for key in node.extra.keys() {
+            assert_eq!(value[key], raw[key], "{key} should round-trip");
+        }
+        assert_eq!(value["pluginData"]["tokens"]["ratio"], 0.5);
+    }
+}
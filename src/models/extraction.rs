@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
-use super::document::EditorType;
+use super::document::{BoundingBox, EditorType, Node, NodeData, TypeStyle};
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -61,6 +61,21 @@ pub struct ExtractedText {
     pub sequence_number: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<TextStyleInfo>,
+    /// Mixed-style spans; empty when the whole text shares one style
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<StyledRun>,
+}
+
+/// A span of text with a single resolved style
+///
+/// `start` and `end` are character offsets into the parent text (end exclusive).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StyledRun {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub style: TextStyleInfo,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -80,6 +95,69 @@ pub struct TextStyleInfo {
     pub font_family: String,
     pub font_size: f64,
     pub font_weight: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_px: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_percent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter_spacing: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_align_horizontal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_align_vertical: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_case: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_decoration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paragraph_spacing: Option<f64>,
+}
+
+impl TextStyleInfo {
+    pub fn new(font_family: String, font_size: f64, font_weight: u16) -> Self {
+        Self {
+            font_family,
+            font_size,
+            font_weight,
+            italic: None,
+            line_height_px: None,
+            line_height_percent: None,
+            line_height_unit: None,
+            letter_spacing: None,
+            text_align_horizontal: None,
+            text_align_vertical: None,
+            text_case: None,
+            text_decoration: None,
+            paragraph_spacing: None,
+        }
+    }
+}
+
+impl From<&TypeStyle> for TextStyleInfo {
+    fn from(style: &TypeStyle) -> Self {
+        Self {
+            font_family: style
+                .font_family
+                .clone()
+                .unwrap_or_else(|| "Unknown".to_string()),
+            font_size: style.font_size.unwrap_or(16.0),
+            font_weight: style.font_weight.unwrap_or(400),
+            italic: style.italic,
+            line_height_px: style.line_height_px,
+            line_height_percent: style.line_height_percent,
+            line_height_unit: style.line_height_unit.clone(),
+            letter_spacing: style.letter_spacing,
+            text_align_horizontal: style.text_align_horizontal.clone(),
+            text_align_vertical: style.text_align_vertical.clone(),
+            text_case: style.text_case.clone(),
+            text_decoration: style.text_decoration.clone(),
+            paragraph_spacing: style.paragraph_spacing,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -100,6 +178,15 @@ pub struct Dimensions {
     pub height: f64,
 }
 
+impl From<&BoundingBox> for Dimensions {
+    fn from(bb: &BoundingBox) -> Self {
+        Self {
+            width: bb.width,
+            height: bb.height,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImageReference {
@@ -113,6 +200,37 @@ pub struct ImageReference {
     pub dimensions: Option<Dimensions>,
 }
 
+/// Original bitmap behind `IMAGE` paints, with every node that uses it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageFill {
+    pub image_ref: String,
+    pub nodes: Vec<ImageFillUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageFillUsage {
+    pub node_id: String,
+    pub node_name: String,
+    pub path: HierarchyPath,
+}
+
+/// Manifest written by `images --fills`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageFillManifest {
+    pub file_key: String,
+    pub file_name: String,
+    pub version: String,
+    pub images: Vec<ImageFill>,
+    pub total: usize,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExtractionStats {
@@ -179,6 +297,37 @@ impl HierarchyPath {
         }
     }
 
+    /// Build a path from a node's ancestors, starting at its page
+    ///
+    /// Nested sections are joined with " > "; any non-container ancestors are ignored.
+    pub fn from_ancestors(ancestors: &[&Node]) -> Self {
+        let mut page_name = None;
+        let mut sections = Vec::new();
+        let mut frame_names = Vec::new();
+        let mut group_names = Vec::new();
+
+        for node in ancestors {
+            let name = node.name().to_string();
+            match &node.data {
+                NodeData::Canvas { .. } => page_name = Some(name),
+                NodeData::Section { .. } => sections.push(name),
+                NodeData::Frame { .. }
+                | NodeData::Component { .. }
+                | NodeData::ComponentSet { .. }
+                | NodeData::Instance { .. } => frame_names.push(name),
+                NodeData::Group { .. } => group_names.push(name),
+                _ => {}
+            }
+        }
+
+        Self {
+            page_name: page_name.unwrap_or_else(|| "Unknown".to_string()),
+            section_name: (!sections.is_empty()).then(|| sections.join(" > ")),
+            frame_names,
+            group_names: (!group_names.is_empty()).then_some(group_names),
+        }
+    }
+
     pub fn with_section(mut self, section: String) -> Self {
         self.section_name = Some(section);
         self
@@ -205,6 +354,7 @@ impl HierarchyPath {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::document::{Appearance, Layout};
 
     #[test]
     fn test_hierarchy_path_to_string() {
@@ -227,6 +377,80 @@ mod tests {
         assert_eq!(path.to_path_string(), "Page1 > Frame1 > Group1 > Group2");
     }
 
+    fn container(node_type: &str, name: &str) -> Node {
+        let data = match node_type {
+            "CANVAS" => NodeData::Canvas {
+                background_color: None,
+                export_settings: vec![],
+                children: vec![],
+            },
+            "SECTION" => NodeData::Section {
+                absolute_bounding_box: None,
+                absolute_render_bounds: None,
+                fills: vec![],
+                strokes: vec![],
+                stroke_weight: 0.0,
+                stroke_align: String::new(),
+                section_contents_hidden: false,
+                children: vec![],
+            },
+            "GROUP" => NodeData::Group {
+                children: vec![],
+                absolute_bounding_box: None,
+                appearance: Appearance::default(),
+            },
+            _ => NodeData::Frame {
+                children: vec![],
+                absolute_bounding_box: None,
+                fills: vec![],
+                clips_content: false,
+                layout: Layout::default(),
+                appearance: Appearance::default(),
+            },
+        };
+
+        Node {
+            base: crate::models::document::NodeBase {
+                node_type: node_type.to_string(),
+                id: "0:0".to_string(),
+                name: name.to_string(),
+                visible: true,
+                locked: false,
+            },
+            data,
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_path_from_ancestors() {
+        let page = container("CANVAS", "Mobile");
+        let section = container("SECTION", "Checkout");
+        let frame = container("FRAME", "Section header");
+        let group = container("GROUP", "Actions");
+
+        let path = HierarchyPath::from_ancestors(&[&page, &section, &frame, &group]);
+
+        assert_eq!(path.page_name, "Mobile");
+        assert_eq!(path.section_name.as_deref(), Some("Checkout"));
+        assert_eq!(path.frame_names, vec!["Section header"]);
+        assert_eq!(path.group_names, Some(vec!["Actions".to_string()]));
+    }
+
+    #[test]
+    fn test_hierarchy_path_nested_sections() {
+        let page = container("CANVAS", "Web");
+        let outer = container("SECTION", "Flows");
+        let inner = container("SECTION", "Onboarding");
+
+        let path = HierarchyPath::from_ancestors(&[&page, &outer, &inner]);
+
+        assert_eq!(path.section_name.as_deref(), Some("Flows > Onboarding"));
+        assert!(path.frame_names.is_empty());
+        assert!(path.group_names.is_none());
+        assert_eq!(HierarchyPath::from_ancestors(&[]).page_name, "Unknown");
+    }
+
     #[test]
     fn test_extraction_result_builder() {
         let metadata = FileMetadata {
@@ -247,6 +471,7 @@ mod tests {
             path: HierarchyPath::new("Page1".to_string(), vec![]),
             sequence_number: 0,
             style: None,
+            runs: vec![],
         }];
 
         let result = ExtractionResult::new(metadata, structure, texts);
@@ -1,8 +1,12 @@
 //! Data models
 
+pub mod comment;
 pub mod config;
+pub mod diff;
 pub mod document;
 pub mod extraction;
+pub mod project;
+pub mod webhook;
 
 pub use config::FilterCriteria;
 pub use document::FigmaFile;
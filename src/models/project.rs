@@ -0,0 +1,63 @@
+//! Team, project and branch listing structures
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Projects of a team
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectListing {
+    pub team_id: String,
+    pub team_name: String,
+    pub projects: Vec<ListedProject>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedProject {
+    pub id: String,
+    pub name: String,
+}
+
+/// Files of one project, or of every project in a team
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileListing {
+    pub files: Vec<ListedFile>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedFile {
+    pub key: String,
+    pub name: String,
+    pub last_modified: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    pub project_id: String,
+    pub project_name: String,
+}
+
+/// Branches of a file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchListing {
+    pub file_key: String,
+    pub file_name: String,
+    pub branches: Vec<ListedBranch>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedBranch {
+    pub key: String,
+    pub name: String,
+    pub last_modified: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_url: Option<String>,
+}
@@ -0,0 +1,157 @@
+//! Figma webhook payloads
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Body of a webhook request
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebhookPayload {
+    /// Secret set when the webhook was created; never written back out
+    #[serde(skip_serializing)]
+    pub passcode: String,
+    pub timestamp: DateTime<Utc>,
+    pub webhook_id: String,
+    #[serde(flatten)]
+    pub event: WebhookEvent,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "event_type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WebhookEvent {
+    /// Sent once when a webhook is created
+    Ping,
+    FileUpdate {
+        file_key: String,
+        file_name: String,
+    },
+    FileVersionUpdate {
+        file_key: String,
+        file_name: String,
+        version_id: String,
+        #[serde(default)]
+        label: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        triggered_by: Option<WebhookUser>,
+    },
+    FileComment {
+        file_key: String,
+        file_name: String,
+        comment_id: String,
+        #[serde(default)]
+        comment: Vec<CommentFragment>,
+        #[serde(default)]
+        triggered_by: Option<WebhookUser>,
+    },
+    LibraryPublish {
+        file_key: String,
+        file_name: String,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        created_components: Vec<LibraryItem>,
+        #[serde(default)]
+        modified_components: Vec<LibraryItem>,
+        #[serde(default)]
+        deleted_components: Vec<LibraryItem>,
+        #[serde(default)]
+        created_styles: Vec<LibraryItem>,
+        #[serde(default)]
+        modified_styles: Vec<LibraryItem>,
+        #[serde(default)]
+        deleted_styles: Vec<LibraryItem>,
+        #[serde(default)]
+        triggered_by: Option<WebhookUser>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebhookUser {
+    pub id: String,
+    pub handle: String,
+}
+
+/// Piece of a comment; either plain text or a user mention
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CommentFragment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mention: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LibraryItem {
+    pub key: String,
+    pub name: String,
+}
+
+impl WebhookEvent {
+    /// Figma's name for the event, e.g. `FILE_VERSION_UPDATE`
+    pub const fn event_type(&self) -> &'static str {
+        match self {
+            Self::Ping => "PING",
+            Self::FileUpdate { .. } => "FILE_UPDATE",
+            Self::FileVersionUpdate { .. } => "FILE_VERSION_UPDATE",
+            Self::FileComment { .. } => "FILE_COMMENT",
+            Self::LibraryPublish { .. } => "LIBRARY_PUBLISH",
+        }
+    }
+
+    pub fn file_key(&self) -> Option<&str> {
+        match self {
+            Self::Ping => None,
+            Self::FileUpdate { file_key, .. }
+            | Self::FileVersionUpdate { file_key, .. }
+            | Self::FileComment { file_key, .. }
+            | Self::LibraryPublish { file_key, .. } => Some(file_key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_payloads() {
+        let payload: WebhookPayload = serde_json::from_value(json!({
+            "event_type": "FILE_VERSION_UPDATE",
+            "passcode": "secret",
+            "timestamp": "2025-01-15T10:30:00Z",
+            "webhook_id": "22",
+            "file_key": "ABCDEFGHIJKLMNOPQRSTUV",
+            "file_name": "Checkout",
+            "version_id": "4242",
+            "label": "Sign-off",
+            "created_at": "2025-01-15T10:30:00Z",
+            "triggered_by": { "id": "7", "handle": "Ana" }
+        }))
+        .unwrap();
+        assert_eq!(payload.event.event_type(), "FILE_VERSION_UPDATE");
+        assert_eq!(payload.event.file_key(), Some("ABCDEFGHIJKLMNOPQRSTUV"));
+
+        let payload: WebhookPayload = serde_json::from_value(json!({
+            "event_type": "FILE_COMMENT",
+            "passcode": "secret",
+            "timestamp": "2025-01-15T10:30:00Z",
+            "webhook_id": "22",
+            "file_key": "ABCDEFGHIJKLMNOPQRSTUV",
+            "file_name": "Checkout",
+            "comment_id": "10",
+            "comment": [{ "text": "Looks good " }, { "mention": "7" }]
+        }))
+        .unwrap();
+        let WebhookEvent::FileComment { comment, .. } = &payload.event else {
+            panic!("expected a comment event");
+        };
+        assert_eq!(comment[1].mention.as_deref(), Some("7"));
+
+        // The passcode never leaves the process
+        let logged = serde_json::to_value(&payload).unwrap();
+        assert!(logged.get("passcode").is_none());
+        assert_eq!(logged["event_type"], "FILE_COMMENT");
+    }
+}
@@ -0,0 +1,197 @@
+//! Comment threading and node resolution
+
+use crate::client::Comment;
+use crate::models::comment::{
+    CommentAnchor, CommentFilter, CommentReply, CommentReport, CommentThread,
+};
+use crate::models::config::FilterCriteria;
+use crate::models::document::{FigmaFile, Node};
+use crate::models::extraction::HierarchyPath;
+use crate::service::traversal::{NodeVisitor, traverse_document};
+use std::collections::{HashMap, HashSet};
+
+/// Name and path of every node, hidden ones included
+#[derive(Default)]
+struct NodeLocator {
+    nodes: HashMap<String, (String, HierarchyPath)>,
+}
+
+impl NodeVisitor for NodeLocator {
+    fn visit_node(&mut self, node: &Node, _depth: usize, path: &[&Node]) {
+        self.nodes.insert(
+            node.id().to_string(),
+            (node.name().to_string(), HierarchyPath::from_ancestors(path)),
+        );
+    }
+}
+
+/// Group comments into threads, pin them to nodes and apply the filter
+///
+/// Replies whose parent is missing from the response are kept as threads of their own.
+pub fn build_report(
+    file: &FigmaFile,
+    comments: Vec<Comment>,
+    filter: &CommentFilter,
+) -> CommentReport {
+    let mut locator = NodeLocator::default();
+    let all_nodes = FilterCriteria::new().with_include_hidden(true);
+    traverse_document(&file.document, &all_nodes, &mut locator);
+
+    let ids: HashSet<String> = comments.iter().map(|c| c.id.clone()).collect();
+    let mut replies: HashMap<String, Vec<CommentReply>> = HashMap::new();
+    let mut roots = Vec::new();
+
+    for comment in comments {
+        match comment.parent() {
+            Some(parent) if ids.contains(parent) => {
+                replies
+                    .entry(parent.to_string())
+                    .or_default()
+                    .push(CommentReply {
+                        id: comment.id,
+                        author: comment.user.handle,
+                        message: comment.message,
+                        created_at: comment.created_at,
+                    });
+            }
+            _ => roots.push(comment),
+        }
+    }
+
+    let mut threads: Vec<CommentThread> = roots
+        .into_iter()
+        .map(|comment| {
+            let anchor = comment
+                .client_meta
+                .and_then(|meta| meta.node_id)
+                .map(|node_id| {
+                    let located = locator.nodes.get(&node_id).cloned();
+                    CommentAnchor {
+                        node_id,
+                        name: located.as_ref().map(|(name, _)| name.clone()),
+                        path: located.map(|(_, path)| path),
+                    }
+                });
+            let mut thread_replies = replies.remove(&comment.id).unwrap_or_default();
+            thread_replies.sort_by_key(|r| r.created_at);
+
+            CommentThread {
+                id: comment.id,
+                number: comment.order_id,
+                author: comment.user.handle,
+                message: comment.message,
+                created_at: comment.created_at,
+                resolved_at: comment.resolved_at,
+                anchor,
+                replies: thread_replies,
+            }
+        })
+        .filter(|thread| filter.matches(thread))
+        .collect();
+    threads.sort_by_key(|t| t.created_at);
+
+    CommentReport {
+        file_key: file.file_key.clone(),
+        file_name: file.name.clone(),
+        threads,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_file() -> FigmaFile {
+        serde_json::from_value(json!({
+            "name": "Checkout",
+            "version": "1",
+            "lastModified": "2025-01-15T10:30:00Z",
+            "editorType": "figma",
+            "document": {
+                "id": "0:0", "name": "Document", "type": "DOCUMENT",
+                "children": [{
+                    "id": "0:1", "name": "Mobile", "type": "CANVAS",
+                    "children": [{
+                        "id": "1:1", "name": "Cart", "type": "FRAME",
+                        "children": [{ "id": "1:2", "name": "Title", "type": "TEXT", "characters": "Cart" }]
+                    }]
+                }]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn comment(id: &str, parent: &str, handle: &str, created: &str, node: Option<&str>) -> Comment {
+        serde_json::from_value(json!({
+            "id": id,
+            "parent_id": parent,
+            "user": { "id": handle, "handle": handle },
+            "message": format!("message {id}"),
+            "created_at": created,
+            "resolved_at": null,
+            "client_meta": node.map(|n| json!({ "node_id": n, "node_offset": { "x": 1.0, "y": 2.0 } }))
+        }))
+        .unwrap()
+    }
+
+    fn sample_comments() -> Vec<Comment> {
+        let mut resolved = comment("3", "", "ben", "2025-01-12T09:00:00Z", None);
+        resolved.resolved_at = Some("2025-01-13T09:00:00Z".parse().unwrap());
+        vec![
+            comment("2", "1", "ben", "2025-01-11T09:00:00Z", None),
+            comment("1", "", "ana", "2025-01-10T09:00:00Z", Some("1:2")),
+            resolved,
+            comment("4", "", "ana", "2025-01-14T09:00:00Z", Some("9:9")),
+        ]
+    }
+
+    #[test]
+    fn test_threads_and_anchors() {
+        let report = build_report(&sample_file(), sample_comments(), &CommentFilter::new());
+
+        assert_eq!(report.threads.len(), 3);
+        let first = &report.threads[0];
+        assert_eq!(first.id, "1");
+        assert_eq!(first.replies.len(), 1);
+        assert_eq!(first.replies[0].author, "ben");
+
+        let anchor = first.anchor.as_ref().unwrap();
+        assert_eq!(anchor.name.as_deref(), Some("Title"));
+        assert_eq!(
+            anchor.path.as_ref().unwrap().to_path_string(),
+            "Mobile > Cart"
+        );
+
+        // Deleted node keeps its id but has no name
+        let orphan = report.threads[2].anchor.as_ref().unwrap();
+        assert_eq!(orphan.node_id, "9:9");
+        assert!(orphan.name.is_none());
+    }
+
+    #[test]
+    fn test_filters() {
+        let file = sample_file();
+
+        let open = build_report(
+            &file,
+            sample_comments(),
+            &CommentFilter::new().with_resolved(false),
+        );
+        assert_eq!(open.threads.len(), 2);
+
+        let by_ana = build_report(
+            &file,
+            sample_comments(),
+            &CommentFilter::new().with_author("ANA".to_string()),
+        );
+        assert!(by_ana.threads.iter().all(|t| t.author == "ana"));
+
+        let window = CommentFilter::new()
+            .with_since("2025-01-11T00:00:00Z".parse().unwrap())
+            .with_until("2025-01-14T00:00:00Z".parse().unwrap());
+        let report = build_report(&file, sample_comments(), &window);
+        assert_eq!(report.threads.len(), 1);
+        assert_eq!(report.threads[0].id, "3");
+    }
+}
@@ -0,0 +1,467 @@
+//! Structural and copy diff between two versions of a file
+
+use crate::models::diff::{Change, DiffSide, DiffSummary, FileDiff, NodeDiff, NodeStatus};
+use crate::models::document::{FigmaFile, Node};
+use crate::models::extraction::{Dimensions, HierarchyPath};
+use std::collections::{HashMap, HashSet};
+
+/// Sizes closer than this are treated as equal
+const SIZE_TOLERANCE: f64 = 0.01;
+
+struct IndexedNode<'a> {
+    node: &'a Node,
+    parent: Option<&'a str>,
+    path: HierarchyPath,
+}
+
+/// Every node of a file by id, plus document order
+#[derive(Default)]
+struct NodeIndex<'a> {
+    order: Vec<&'a str>,
+    nodes: HashMap<&'a str, IndexedNode<'a>>,
+}
+
+impl<'a> NodeIndex<'a> {
+    fn build(file: &'a FigmaFile) -> Self {
+        let mut index = Self::default();
+        let mut ancestors = Vec::new();
+        for page in &file.document.children {
+            index.insert(page, &mut ancestors);
+        }
+        index
+    }
+
+    fn insert(&mut self, node: &'a Node, ancestors: &mut Vec<&'a Node>) {
+        self.order.push(node.id());
+        self.nodes.insert(
+            node.id(),
+            IndexedNode {
+                node,
+                parent: ancestors.last().map(|n| n.id()),
+                path: HierarchyPath::from_ancestors(ancestors),
+            },
+        );
+
+        if let Some(children) = node.children() {
+            ancestors.push(node);
+            for child in children {
+                self.insert(child, ancestors);
+            }
+            ancestors.pop();
+        }
+    }
+
+    /// Identity used when ids do not line up, e.g. after copy-paste
+    fn fallback_key(&self, id: &str) -> (String, String, String) {
+        let entry = &self.nodes[id];
+        (
+            entry.node.node_type_str().to_string(),
+            entry.node.name().to_string(),
+            entry.path.to_path_string(),
+        )
+    }
+}
+
+/// Compare two files node by node
+///
+/// Nodes are matched by id first. Leftovers are then paired when their type, name
+/// and hierarchy path match exactly one node on the other side.
+pub fn diff_files(old: &FigmaFile, new: &FigmaFile) -> FileDiff {
+    let old_index = NodeIndex::build(old);
+    let new_index = NodeIndex::build(new);
+
+    let mut old_to_new: HashMap<&str, &str> = new_index
+        .order
+        .iter()
+        .filter(|id| old_index.nodes.contains_key(*id))
+        .map(|id| (*id, *id))
+        .collect();
+    pair_by_path(&old_index, &new_index, &mut old_to_new);
+    let new_to_old: HashMap<&str, &str> = old_to_new.iter().map(|(o, n)| (*n, *o)).collect();
+
+    let mut nodes = Vec::new();
+    let mut summary = DiffSummary::default();
+
+    let mut added_roots: HashMap<&str, usize> = HashMap::new();
+    for &id in &new_index.order {
+        let entry = &new_index.nodes[id];
+        match new_to_old.get(id) {
+            Some(&old_id) => {
+                let before = &old_index.nodes[old_id];
+                let changes = compare(before, entry, &old_to_new);
+                if !changes.is_empty() {
+                    count_changes(&mut summary, &changes);
+                    let mut diff = node_diff(entry, NodeStatus::Changed);
+                    diff.previous_id = (old_id != id).then(|| old_id.to_string());
+                    diff.changes = changes;
+                    nodes.push(diff);
+                }
+            }
+            None => {
+                if let Some(&root) = entry.parent.and_then(|p| added_roots.get(p)) {
+                    nodes[root].descendants += 1;
+                    added_roots.insert(id, root);
+                } else {
+                    summary.added += 1;
+                    added_roots.insert(id, nodes.len());
+                    nodes.push(node_diff(entry, NodeStatus::Added));
+                }
+            }
+        }
+    }
+
+    let mut removed_roots: HashMap<&str, usize> = HashMap::new();
+    for &id in &old_index.order {
+        if old_to_new.contains_key(id) {
+            continue;
+        }
+        let entry = &old_index.nodes[id];
+        if let Some(&root) = entry.parent.and_then(|p| removed_roots.get(p)) {
+            nodes[root].descendants += 1;
+            removed_roots.insert(id, root);
+        } else {
+            summary.removed += 1;
+            removed_roots.insert(id, nodes.len());
+            nodes.push(node_diff(entry, NodeStatus::Removed));
+        }
+    }
+
+    FileDiff {
+        file_key: new.file_key.clone(),
+        file_name: new.name.clone(),
+        from: side(old),
+        to: side(new),
+        summary,
+        nodes,
+    }
+}
+
+fn pair_by_path<'a>(
+    old_index: &NodeIndex<'a>,
+    new_index: &NodeIndex<'a>,
+    old_to_new: &mut HashMap<&'a str, &'a str>,
+) {
+    fn unique_keys<'a>(
+        index: &NodeIndex<'a>,
+        ids: impl Iterator<Item = &'a str>,
+    ) -> HashMap<(String, String, String), Option<&'a str>> {
+        let mut keys = HashMap::new();
+        for id in ids {
+            keys.entry(index.fallback_key(id))
+                .and_modify(|slot| *slot = None)
+                .or_insert(Some(id));
+        }
+        keys
+    }
+
+    let matched_new: HashSet<&str> = old_to_new.values().copied().collect();
+    let old_keys = unique_keys(
+        old_index,
+        old_index
+            .order
+            .iter()
+            .copied()
+            .filter(|id| !old_to_new.contains_key(id)),
+    );
+    let new_keys = unique_keys(
+        new_index,
+        new_index
+            .order
+            .iter()
+            .copied()
+            .filter(|id| !matched_new.contains(id)),
+    );
+
+    for (key, old_id) in old_keys {
+        if let (Some(old_id), Some(Some(new_id))) = (old_id, new_keys.get(&key)) {
+            old_to_new.insert(old_id, new_id);
+        }
+    }
+}
+
+fn compare(
+    before: &IndexedNode<'_>,
+    after: &IndexedNode<'_>,
+    old_to_new: &HashMap<&str, &str>,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let (old, new) = (before.node, after.node);
+
+    let old_parent = before.parent.and_then(|p| old_to_new.get(p).copied());
+    if old_parent != after.parent {
+        changes.push(Change::Moved {
+            from: before.path.clone(),
+            to: after.path.clone(),
+        });
+    }
+
+    if old.name() != new.name() {
+        changes.push(Change::Renamed {
+            from: old.name().to_string(),
+            to: new.name().to_string(),
+        });
+    }
+
+    if old.characters() != new.characters() {
+        changes.push(Change::Characters {
+            from: old.characters().unwrap_or_default().to_string(),
+            to: new.characters().unwrap_or_default().to_string(),
+        });
+    }
+
+    if old.fills() != new.fills() {
+        changes.push(Change::Fills {
+            from: old.fills().unwrap_or_default().to_vec(),
+            to: new.fills().unwrap_or_default().to_vec(),
+        });
+    }
+
+    let old_size = old.absolute_bounding_box().map(Dimensions::from);
+    let new_size = new.absolute_bounding_box().map(Dimensions::from);
+    if !same_size(old_size, new_size) {
+        changes.push(Change::Size {
+            from: old_size,
+            to: new_size,
+        });
+    }
+
+    if old.style() != new.style() {
+        changes.push(Change::Style {
+            from: old.style().cloned().map(Box::new),
+            to: new.style().cloned().map(Box::new),
+        });
+    }
+
+    changes
+}
+
+fn same_size(a: Option<Dimensions>, b: Option<Dimensions>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            (a.width - b.width).abs() < SIZE_TOLERANCE
+                && (a.height - b.height).abs() < SIZE_TOLERANCE
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn count_changes(summary: &mut DiffSummary, changes: &[Change]) {
+    for change in changes {
+        let counter = match change {
+            Change::Moved { .. } => &mut summary.moved,
+            Change::Renamed { .. } => &mut summary.renamed,
+            Change::Characters { .. } => &mut summary.characters,
+            Change::Fills { .. } => &mut summary.fills,
+            Change::Size { .. } => &mut summary.size,
+            Change::Style { .. } => &mut summary.style,
+        };
+        *counter += 1;
+    }
+}
+
+fn node_diff(entry: &IndexedNode<'_>, status: NodeStatus) -> NodeDiff {
+    let characters = match status {
+        NodeStatus::Changed => None,
+        NodeStatus::Added | NodeStatus::Removed => entry.node.characters().map(str::to_string),
+    };
+
+    NodeDiff {
+        node_id: entry.node.id().to_string(),
+        previous_id: None,
+        node_type: entry.node.node_type_str().to_string(),
+        name: entry.node.name().to_string(),
+        path: entry.path.clone(),
+        status,
+        descendants: 0,
+        characters,
+        changes: Vec::new(),
+    }
+}
+
+fn side(file: &FigmaFile) -> DiffSide {
+    DiffSide {
+        version: file.version.clone(),
+        last_modified: file.last_modified,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn file(version: &str, pages: serde_json::Value) -> FigmaFile {
+        serde_json::from_value(json!({
+            "name": "Checkout",
+            "version": version,
+            "lastModified": "2025-01-15T10:30:00Z",
+            "editorType": "figma",
+            "document": { "id": "0:0", "name": "Document", "type": "DOCUMENT", "children": pages }
+        }))
+        .unwrap()
+    }
+
+    fn text(id: &str, name: &str, characters: &str) -> serde_json::Value {
+        json!({ "id": id, "name": name, "type": "TEXT", "characters": characters })
+    }
+
+    fn frame(id: &str, name: &str, width: f64, children: serde_json::Value) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": name,
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0, "y": 0, "width": width, "height": 100 },
+            "children": children
+        })
+    }
+
+    fn page(children: serde_json::Value) -> serde_json::Value {
+        json!([{ "id": "0:1", "name": "Mobile", "type": "CANVAS", "children": children }])
+    }
+
+    #[test]
+    fn test_identical_files_have_no_changes() {
+        let a = file("1", page(json!([frame("1:1", "Cart", 375.0, json!([]))])));
+        let diff = diff_files(&a, &a);
+
+        assert!(diff.nodes.is_empty());
+        assert_eq!(diff.summary.added, 0);
+    }
+
+    #[test]
+    fn test_copy_rename_and_resize() {
+        let old = file(
+            "1",
+            page(json!([frame(
+                "1:1",
+                "Cart",
+                375.0,
+                json!([text("1:2", "Title", "Your basket")])
+            )])),
+        );
+        let new = file(
+            "2",
+            page(json!([frame(
+                "1:1",
+                "Basket",
+                390.0,
+                json!([text("1:2", "Title", "Your cart")])
+            )])),
+        );
+        let diff = diff_files(&old, &new);
+
+        assert_eq!(diff.from.version, "1");
+        assert_eq!(diff.nodes.len(), 2);
+        assert!(matches!(
+            diff.nodes[0].changes.as_slice(),
+            [Change::Renamed { .. }, Change::Size { .. }]
+        ));
+        match &diff.nodes[1].changes[..] {
+            [Change::Characters { from, to }] => {
+                assert_eq!(from, "Your basket");
+                assert_eq!(to, "Your cart");
+            }
+            other => panic!("unexpected changes: {other:?}"),
+        }
+        // Parent rename alone does not count as a move
+        assert_eq!(diff.summary.moved, 0);
+    }
+
+    #[test]
+    fn test_added_and_removed_subtrees_collapse() {
+        let old = file(
+            "1",
+            page(json!([frame(
+                "1:1",
+                "Old",
+                375.0,
+                json!([text("1:2", "A", "a"), text("1:3", "B", "b")])
+            )])),
+        );
+        let new = file(
+            "2",
+            page(json!([frame(
+                "2:1",
+                "New",
+                375.0,
+                json!([text("2:2", "C", "c")])
+            )])),
+        );
+        let diff = diff_files(&old, &new);
+
+        assert_eq!(diff.summary.added, 1);
+        assert_eq!(diff.summary.removed, 1);
+        assert_eq!(diff.nodes[0].status, NodeStatus::Added);
+        assert_eq!(diff.nodes[0].descendants, 1);
+        assert_eq!(diff.nodes[1].status, NodeStatus::Removed);
+        assert_eq!(diff.nodes[1].descendants, 2);
+    }
+
+    #[test]
+    fn test_move_between_frames() {
+        let old = file(
+            "1",
+            page(json!([
+                frame(
+                    "1:1",
+                    "Cart",
+                    375.0,
+                    json!([text("1:3", "Note", "Free shipping")])
+                ),
+                frame("1:2", "Payment", 375.0, json!([]))
+            ])),
+        );
+        let new = file(
+            "2",
+            page(json!([
+                frame("1:1", "Cart", 375.0, json!([])),
+                frame(
+                    "1:2",
+                    "Payment",
+                    375.0,
+                    json!([text("1:3", "Note", "Free shipping")])
+                )
+            ])),
+        );
+        let diff = diff_files(&old, &new);
+
+        assert_eq!(diff.summary.moved, 1);
+        match &diff.nodes[0].changes[..] {
+            [Change::Moved { from, to }] => {
+                assert_eq!(from.frame_names, vec!["Cart"]);
+                assert_eq!(to.frame_names, vec!["Payment"]);
+            }
+            other => panic!("unexpected changes: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_recreated_node_matched_by_path() {
+        let old = file(
+            "1",
+            page(json!([frame(
+                "1:1",
+                "Cart",
+                375.0,
+                json!([text("1:2", "Title", "Your basket")])
+            )])),
+        );
+        let new = file(
+            "2",
+            page(json!([frame(
+                "1:1",
+                "Cart",
+                375.0,
+                json!([text("9:9", "Title", "Your cart")])
+            )])),
+        );
+        let diff = diff_files(&old, &new);
+
+        assert_eq!(diff.nodes.len(), 1);
+        assert_eq!(diff.nodes[0].status, NodeStatus::Changed);
+        assert_eq!(diff.nodes[0].node_id, "9:9");
+        assert_eq!(diff.nodes[0].previous_id.as_deref(), Some("1:2"));
+        assert_eq!(diff.summary.characters, 1);
+    }
+}
@@ -0,0 +1,160 @@
+//! Image fills: the original bitmaps behind `IMAGE` paints
+
+use crate::models::config::FilterCriteria;
+use crate::models::document::{FigmaFile, Node};
+use crate::models::extraction::{HierarchyPath, ImageFillUsage};
+use crate::service::traversal::{NodeVisitor, traverse_document};
+use std::collections::{BTreeMap, HashSet};
+
+/// Nodes using each `imageRef`, optionally limited to some subtrees
+struct ImageFillCollector<'a> {
+    roots: &'a HashSet<&'a str>,
+    usages: BTreeMap<String, Vec<ImageFillUsage>>,
+}
+
+impl NodeVisitor for ImageFillCollector<'_> {
+    fn visit_node(&mut self, node: &Node, _depth: usize, path: &[&Node]) {
+        let in_scope = self.roots.is_empty()
+            || self.roots.contains(node.id())
+            || path
+                .iter()
+                .any(|ancestor| self.roots.contains(ancestor.id()));
+        if !in_scope {
+            return;
+        }
+
+        let image_refs: HashSet<&str> = node
+            .fills()
+            .unwrap_or_default()
+            .iter()
+            .filter(|paint| paint.paint_type == "IMAGE")
+            .filter_map(|paint| paint.image_ref.as_deref())
+            .collect();
+
+        for image_ref in image_refs {
+            self.usages
+                .entry(image_ref.to_string())
+                .or_default()
+                .push(ImageFillUsage {
+                    node_id: node.id().to_string(),
+                    node_name: node.name().to_string(),
+                    path: HierarchyPath::from_ancestors(path),
+                });
+        }
+    }
+}
+
+/// Nodes using each `imageRef` in the file, hidden ones included
+///
+/// With `node_ids`, only nodes inside those subtrees are considered.
+pub fn collect_image_fills(
+    file: &FigmaFile,
+    node_ids: &[String],
+) -> BTreeMap<String, Vec<ImageFillUsage>> {
+    let roots: HashSet<&str> = node_ids.iter().map(String::as_str).collect();
+    let mut collector = ImageFillCollector {
+        roots: &roots,
+        usages: BTreeMap::new(),
+    };
+    let all_nodes = FilterCriteria::new().with_include_hidden(true);
+    traverse_document(&file.document, &all_nodes, &mut collector);
+
+    collector.usages
+}
+
+/// File name derived from the image content, so identical bitmaps share one file
+pub fn content_file_name(bytes: &[u8]) -> String {
+    format!(
+        "{}.{}",
+        blake3::hash(bytes).to_hex(),
+        image_extension(bytes)
+    )
+}
+
+/// Extension for the image format, sniffed from its leading bytes
+fn image_extension(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "png"
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "jpg"
+    } else if bytes.starts_with(b"GIF8") {
+        "gif"
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        "webp"
+    } else {
+        "bin"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_file() -> FigmaFile {
+        let image = |image_ref: &str| json!({ "type": "IMAGE", "imageRef": image_ref, "scaleMode": "FILL" });
+        serde_json::from_value(json!({
+            "name": "Campaign",
+            "version": "1",
+            "lastModified": "2025-01-15T10:30:00Z",
+            "editorType": "figma",
+            "document": {
+                "id": "0:0", "name": "Document", "type": "DOCUMENT",
+                "children": [{
+                    "id": "0:1", "name": "Launch", "type": "CANVAS",
+                    "children": [
+                        {
+                            "id": "1:1", "name": "Hero", "type": "FRAME", "fills": [image("photo")],
+                            "children": [
+                                { "id": "1:2", "name": "Portrait", "type": "RECTANGLE", "fills": [image("portrait"), image("photo")] },
+                                { "id": "1:3", "name": "Hidden", "type": "RECTANGLE", "visible": false, "fills": [image("portrait")] }
+                            ]
+                        },
+                        {
+                            "id": "2:1", "name": "Footer", "type": "FRAME",
+                            "children": [{ "id": "2:2", "name": "Logo", "type": "RECTANGLE", "fills": [image("logo")] }]
+                        }
+                    ]
+                }]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_collects_every_node_per_image_ref() {
+        let usages = collect_image_fills(&sample_file(), &[]);
+
+        assert_eq!(usages.len(), 3);
+        let photo: Vec<&str> = usages["photo"].iter().map(|u| u.node_id.as_str()).collect();
+        assert_eq!(photo, ["1:1", "1:2"]);
+        let portrait: Vec<&str> = usages["portrait"]
+            .iter()
+            .map(|u| u.node_id.as_str())
+            .collect();
+        assert_eq!(portrait, ["1:2", "1:3"]);
+        assert_eq!(usages["logo"][0].path.page_name, "Launch");
+        assert_eq!(usages["logo"][0].path.frame_names, ["Footer"]);
+    }
+
+    #[test]
+    fn test_limits_to_subtrees() {
+        let usages = collect_image_fills(&sample_file(), &["2:1".to_string()]);
+        assert_eq!(usages.keys().collect::<Vec<_>>(), ["logo"]);
+    }
+
+    #[test]
+    fn test_content_file_name() {
+        let png = b"\x89PNG\r\n\x1a\nrest";
+        assert!(content_file_name(png).ends_with(".png"));
+        assert_eq!(content_file_name(png), content_file_name(png));
+        assert_ne!(
+            content_file_name(png),
+            content_file_name(b"\x89PNG\r\n\x1a\nother")
+        );
+
+        assert!(content_file_name(&[0xFF, 0xD8, 0xFF, 0xE0]).ends_with(".jpg"));
+        assert!(content_file_name(b"RIFF\0\0\0\0WEBPVP8 ").ends_with(".webp"));
+        assert!(content_file_name(b"plain").ends_with(".bin"));
+    }
+}
@@ -1,7 +1,12 @@
 //! Business logic and orchestration
 
+pub mod comments;
+pub mod diff;
+pub mod image_fills;
 pub mod orchestrator;
 pub mod traversal;
 
+pub use comments::build_report;
+pub use diff::diff_files;
 pub use orchestrator::Orchestrator;
 pub use traversal::{NodeVisitor, traverse_document};
@@ -1,16 +1,26 @@
 //! Main extraction orchestration
 
 use crate::client::{FigmaClient, Result};
-use crate::extractor::TextExtractor;
-use crate::models::config::FilterCriteria;
+use crate::core::errors::Error;
+use crate::extractor::{ElementExtractor, TextExtractor};
+use crate::models::config::{ExtractionOptions, FilterCriteria};
 use crate::models::document::{FigmaFile, Node, NodeData};
 use crate::models::extraction::{
-    DocumentStructure, ExtractionResult, ExtractionStats, FileMetadata, PageInfo,
+    DesignElement, Dimensions, DocumentStructure, ExtractedText, ExtractionResult, ExtractionStats,
+    FileMetadata, HierarchyPath, ImageFill, ImageFillManifest, ImageReference, PageInfo,
 };
-use crate::service::traversal::{traverse_document, traverse_pages};
+use crate::service::image_fills::{collect_image_fills, content_file_name};
+use crate::service::traversal::{NodeVisitor, is_frame, traverse_document, traverse_pages};
 use chrono::Utc;
+use futures::{StreamExt, TryStreamExt, stream};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
+const DEFAULT_IMAGE_DIR: &str = "images";
+
+/// Maximum node IDs per images endpoint request
+const IMAGE_BATCH_SIZE: usize = 50;
+
 pub struct Orchestrator {
     client: FigmaClient,
 }
@@ -20,17 +30,22 @@ impl Orchestrator {
         Self { client }
     }
 
+    pub const fn client(&self) -> &FigmaClient {
+        &self.client
+    }
+
     pub async fn extract(
         &self,
         file_key: &str,
-        filter: FilterCriteria,
+        options: &ExtractionOptions,
         depth: Option<u32>,
+        version: Option<&str>,
     ) -> Result<ExtractionResult> {
         let start_time = Instant::now();
 
         tracing::info!("Starting extraction for file: {}", file_key);
 
-        let file = self.client.get_file(file_key, depth).await?;
+        let file = self.client.get_file(file_key, depth, version).await?;
 
         tracing::info!(
             "File fetched: {} (version: {}, pages: {})",
@@ -39,7 +54,23 @@ impl Orchestrator {
             file.document.children.len()
         );
 
-        let (texts, structure) = self.extract_content(&file, &filter);
+        let (structure, page_ids) = build_structure(&file, &options.filter);
+
+        let mut text_extractor = TextExtractor::new();
+        traverse_filtered(&file, &options.filter, &page_ids, &mut text_extractor);
+        let texts = text_extractor.into_texts();
+
+        let elements = options.include_metadata.then(|| {
+            let mut element_extractor = ElementExtractor::new();
+            traverse_filtered(&file, &options.filter, &page_ids, &mut element_extractor);
+            element_extractor.into_elements()
+        });
+
+        let images = if options.include_images {
+            Some(self.render_frames(&file, &page_ids, options).await?)
+        } else {
+            None
+        };
 
         let extraction_time_ms = start_time.elapsed().as_millis() as u64;
         let total_characters: usize = texts.iter().map(|t| t.text.len()).sum();
@@ -49,9 +80,9 @@ impl Orchestrator {
             total_frames: structure.pages.iter().map(|p| p.frame_count).sum(),
             total_text_nodes: texts.len(),
             total_characters,
-            total_images: None,
+            total_images: images.as_ref().map(Vec::len),
             extraction_time_ms,
-            memory_size_mb: estimate_memory_size(&texts),
+            memory_size_mb: estimate_memory_size(&texts, elements.as_deref()),
         };
 
         let metadata = FileMetadata {
@@ -74,82 +105,260 @@ impl Orchestrator {
             metadata,
             structure,
             texts,
-            elements: None,
-            images: None,
+            elements,
+            images,
             stats,
         })
     }
 
-    fn extract_content(
+    /// Render top-level frames and download them into the image directory
+    async fn render_frames(
         &self,
         file: &FigmaFile,
-        filter: &FilterCriteria,
-    ) -> (
-        Vec<crate::models::extraction::ExtractedText>,
-        DocumentStructure,
-    ) {
-        let mut pages = Vec::new();
-        let mut filtered_page_ids = Vec::new();
-
-        for child in &file.document.children {
-            if let NodeData::Canvas { children, .. } = &child.data {
-                let id = child.id();
-                let name = child.name();
-
-                if !filter.matches_page(name) || !filter.matches_page_id(id) {
-                    continue;
-                }
+        page_ids: &[String],
+        options: &ExtractionOptions,
+    ) -> Result<Vec<ImageReference>> {
+        let frames = collect_top_level_frames(file, page_ids, &options.filter);
+        if frames.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let extension = options.image_format.extension();
+        let image_dir = PathBuf::from(options.image_dir.as_deref().unwrap_or(DEFAULT_IMAGE_DIR));
+        tokio::fs::create_dir_all(&image_dir).await?;
 
-                filtered_page_ids.push(id.to_string());
-                pages.push(PageInfo {
-                    id: id.to_string(),
-                    name: name.to_string(),
-                    frame_count: count_frames(children),
-                    text_node_count: count_text_nodes(children),
-                });
+        tracing::info!("Rendering {} frames as {}", frames.len(), extension);
+
+        let mut urls = std::collections::HashMap::new();
+        for chunk in frames.chunks(IMAGE_BATCH_SIZE) {
+            let ids: Vec<String> = chunk.iter().map(|f| f.node_id.clone()).collect();
+            let response = self
+                .client
+                .get_images(&file.file_key, &ids, extension, options.image_scale)
+                .await?;
+
+            if let Some(err) = response.err {
+                return Err(Error::other(format!("Figma image render failed: {err}")));
             }
+            urls.extend(response.images);
         }
 
-        let structure = DocumentStructure { pages };
+        let renderable = frames.into_iter().filter_map(|mut reference| {
+            if let Some(url) = urls.remove(&reference.node_id).flatten() {
+                reference.image_url = url;
+                Some(reference)
+            } else {
+                tracing::warn!(
+                    "Node '{}' returned null URL (not renderable)",
+                    reference.node_id
+                );
+                None
+            }
+        });
 
-        let mut text_extractor = TextExtractor::new();
-        if filter.is_empty() {
-            traverse_document(&file.document, &mut text_extractor);
-        } else {
-            traverse_pages(&file.document, &filtered_page_ids, &mut text_extractor);
+        stream::iter(renderable)
+            .map(|mut reference| {
+                let image_dir = &image_dir;
+                async move {
+                    let bytes = self.client.download(&reference.image_url).await?;
+                    let path = image_dir.join(format!(
+                        "{}.{extension}",
+                        reference.node_id.replace(':', "-")
+                    ));
+                    tokio::fs::write(&path, &bytes).await?;
+
+                    reference.local_path = Some(path.display().to_string());
+                    Ok::<_, Error>(reference)
+                }
+            })
+            .buffered(options.max_concurrent_images.max(1))
+            .try_collect()
+            .await
+    }
+
+    /// Download the original bitmaps behind image fills, named by a hash of their content
+    ///
+    /// With `node_ids`, only fills used inside those subtrees are fetched; otherwise every image
+    /// in the file is, including ones no node references any more.
+    pub async fn download_image_fills(
+        &self,
+        file_key: &str,
+        node_ids: &[String],
+        image_dir: &Path,
+        max_concurrent: usize,
+    ) -> Result<ImageFillManifest> {
+        let file = self.client.get_file(file_key, None, None).await?;
+        let mut usages = collect_image_fills(&file, node_ids);
+        let mut urls = self.client.get_image_fills(file_key).await?;
+
+        if node_ids.is_empty() {
+            for image_ref in urls.keys() {
+                usages.entry(image_ref.clone()).or_default();
+            }
         }
 
-        (text_extractor.into_texts(), structure)
+        let fills: Vec<ImageFill> = usages
+            .into_iter()
+            .map(|(image_ref, nodes)| ImageFill {
+                image_url: urls.remove(&image_ref),
+                image_ref,
+                nodes,
+                local_path: None,
+            })
+            .collect();
+
+        tokio::fs::create_dir_all(image_dir).await?;
+        tracing::info!("Downloading {} image fills", fills.len());
+
+        let images: Vec<ImageFill> = stream::iter(fills)
+            .map(|mut fill| async move {
+                let Some(url) = &fill.image_url else {
+                    tracing::warn!("No download URL for image fill '{}'", fill.image_ref);
+                    return Ok(fill);
+                };
+
+                let bytes = self.client.download(url).await?;
+                let path = image_dir.join(content_file_name(&bytes));
+                if !path.exists() {
+                    tokio::fs::write(&path, &bytes).await?;
+                }
+
+                fill.local_path = Some(path.display().to_string());
+                Ok::<_, Error>(fill)
+            })
+            .buffered(max_concurrent.max(1))
+            .try_collect()
+            .await?;
+
+        Ok(ImageFillManifest {
+            file_key: file_key.to_string(),
+            file_name: file.name,
+            version: file.version,
+            total: images.len(),
+            images,
+        })
+    }
+}
+
+fn build_structure(file: &FigmaFile, filter: &FilterCriteria) -> (DocumentStructure, Vec<String>) {
+    let mut pages = Vec::new();
+    let mut filtered_page_ids = Vec::new();
+
+    for child in &file.document.children {
+        if let NodeData::Canvas { .. } = &child.data {
+            let id = child.id();
+            let name = child.name();
+
+            if !filter.matches_page(name) || !filter.matches_page_id(id) {
+                continue;
+            }
+
+            let mut counter = PageCounter::default();
+            traverse_pages(&file.document, &[id.to_string()], filter, &mut counter);
+
+            filtered_page_ids.push(id.to_string());
+            pages.push(PageInfo {
+                id: id.to_string(),
+                name: name.to_string(),
+                frame_count: counter.frames,
+                text_node_count: counter.texts.count(),
+            });
+        }
     }
+
+    (DocumentStructure { pages }, filtered_page_ids)
 }
 
-fn count_frames(nodes: &[Node]) -> usize {
-    nodes
-        .iter()
-        .filter(|n| matches!(&n.data, NodeData::Frame { .. }))
-        .count()
+fn traverse_filtered<V: NodeVisitor>(
+    file: &FigmaFile,
+    filter: &FilterCriteria,
+    page_ids: &[String],
+    visitor: &mut V,
+) {
+    if filter.is_empty() {
+        traverse_document(&file.document, filter, visitor);
+    } else {
+        traverse_pages(&file.document, page_ids, filter, visitor);
+    }
 }
 
-fn count_text_nodes(nodes: &[Node]) -> usize {
-    nodes.iter().fold(0, |count, node| {
-        count
-            + match &node.data {
-                NodeData::Text { .. } | NodeData::Sticky { .. } => 1,
-                NodeData::Frame { children, .. }
-                | NodeData::Group { children, .. }
-                | NodeData::Section { children, .. }
-                | NodeData::Component { children, .. }
-                | NodeData::ComponentSet { children, .. }
-                | NodeData::Instance { children, .. } => count_text_nodes(children),
-                _ => 0,
+/// Frames directly on a page, plus frames directly inside top-level sections
+fn collect_top_level_frames(
+    file: &FigmaFile,
+    page_ids: &[String],
+    filter: &FilterCriteria,
+) -> Vec<ImageReference> {
+    let selected = |node: &&Node| {
+        is_frame(node)
+            && (filter.include_hidden || node.is_visible())
+            && filter.matches_frame(node.name())
+    };
+    let mut frames = Vec::new();
+
+    for page in &file.document.children {
+        if !page_ids.iter().any(|id| id == page.id()) {
+            continue;
+        }
+
+        for child in page.children().unwrap_or_default() {
+            if !filter.include_hidden && !child.is_visible() {
+                continue;
             }
-    })
+
+            if let NodeData::Section { children, .. } = &child.data {
+                frames.extend(
+                    children
+                        .iter()
+                        .filter(selected)
+                        .map(|n| image_reference(n, &[page, child])),
+                );
+            } else if selected(&child) {
+                frames.push(image_reference(child, &[page]));
+            }
+        }
+    }
+
+    frames
 }
 
-fn estimate_memory_size(texts: &[crate::models::extraction::ExtractedText]) -> f64 {
+fn image_reference(node: &Node, path: &[&Node]) -> ImageReference {
+    ImageReference {
+        node_id: node.id().to_string(),
+        node_name: node.name().to_string(),
+        path: HierarchyPath::from_ancestors(path),
+        image_url: String::new(),
+        local_path: None,
+        dimensions: node.absolute_bounding_box().map(Dimensions::from),
+    }
+}
+
+/// Per-page counts computed with the same filter as the extraction itself
+#[derive(Default)]
+struct PageCounter {
+    frames: usize,
+    texts: TextExtractor,
+}
+
+impl NodeVisitor for PageCounter {
+    fn visit_node(&mut self, node: &Node, depth: usize, path: &[&Node]) {
+        // Same frames as `collect_top_level_frames`: on the page or directly in a top-level section
+        let top_level = match path {
+            [_page] => true,
+            [_page, parent] => matches!(&parent.data, NodeData::Section { .. }),
+            _ => false,
+        };
+        if top_level && is_frame(node) {
+            self.frames += 1;
+        }
+        self.texts.visit_node(node, depth, path);
+    }
+}
+
+fn estimate_memory_size(texts: &[ExtractedText], elements: Option<&[DesignElement]>) -> f64 {
     let text_bytes: usize = texts.iter().map(|t| t.text.len()).sum();
+    let element_count = elements.map_or(0, <[DesignElement]>::len);
     let overhead_per_item = 200;
-    let total_bytes = text_bytes + (texts.len() * overhead_per_item);
+    let total_bytes = text_bytes + ((texts.len() + element_count) * overhead_per_item);
     (total_bytes as f64) / (1024.0 * 1024.0)
 }
 
@@ -159,7 +368,7 @@ mod tests {
 
     #[test]
     fn test_memory_estimation() {
-        use crate::models::extraction::{ExtractedText, HierarchyPath, TextNodeType};
+        use crate::models::extraction::{HierarchyPath, TextNodeType};
 
         let texts = vec![
             ExtractedText {
@@ -169,12 +378,48 @@ mod tests {
                 path: HierarchyPath::new("Page".to_string(), vec![]),
                 sequence_number: 0,
                 style: None,
+                runs: vec![],
             };
             10
         ];
 
-        let size_mb = estimate_memory_size(&texts);
+        let size_mb = estimate_memory_size(&texts, None);
         assert!(size_mb > 0.0);
         assert!(size_mb < 1.0);
     }
+
+    #[test]
+    fn test_frame_count_matches_rendered_frames() {
+        let file: FigmaFile = serde_json::from_value(serde_json::json!({
+            "name": "Library",
+            "version": "1",
+            "lastModified": "2025-01-15T10:30:00Z",
+            "editorType": "figma",
+            "document": {
+                "id": "0:0", "name": "Document", "type": "DOCUMENT",
+                "children": [{
+                    "id": "0:1", "name": "Components", "type": "CANVAS",
+                    "children": [
+                        { "id": "1:1", "name": "Button", "type": "COMPONENT", "children": [] },
+                        {
+                            "id": "2:1", "name": "Screens", "type": "SECTION",
+                            "children": [{
+                                "id": "2:2", "name": "Checkout", "type": "FRAME",
+                                "children": [{ "id": "2:3", "name": "Nested", "type": "FRAME", "children": [] }]
+                            }]
+                        },
+                        { "id": "3:1", "name": "Loose", "type": "RECTANGLE" }
+                    ]
+                }]
+            }
+        }))
+        .unwrap();
+        let filter = FilterCriteria::new();
+
+        let (structure, page_ids) = build_structure(&file, &filter);
+        let rendered = collect_top_level_frames(&file, &page_ids, &filter);
+
+        assert_eq!(structure.pages[0].frame_count, 2);
+        assert_eq!(structure.pages[0].frame_count, rendered.len());
+    }
 }
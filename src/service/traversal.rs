@@ -1,61 +1,113 @@
 //! Document tree traversal
 
+use crate::models::config::FilterCriteria;
 use crate::models::document::{Document, Node, NodeData};
 
 pub trait NodeVisitor {
-    fn visit_node(&mut self, node: &Node, depth: usize, path: &[String]);
+    /// Visit a node; `path` holds its ancestors from the page down to the parent
+    fn visit_node(&mut self, node: &Node, depth: usize, path: &[&Node]);
 }
 
-pub fn traverse_document<V: NodeVisitor>(document: &Document, visitor: &mut V) {
-    let mut path = vec![document.name.clone()];
+pub fn traverse_document<V: NodeVisitor>(
+    document: &Document,
+    filter: &FilterCriteria,
+    visitor: &mut V,
+) {
+    let mut path = Vec::new();
 
     for child in &document.children {
-        traverse_node(child, visitor, 1, &mut path);
+        traverse_node(child, visitor, filter, 1, &mut path, false);
     }
 }
 
-pub fn traverse_pages<V: NodeVisitor>(document: &Document, page_ids: &[String], visitor: &mut V) {
-    let mut path = vec![document.name.clone()];
+pub fn traverse_pages<V: NodeVisitor>(
+    document: &Document,
+    page_ids: &[String],
+    filter: &FilterCriteria,
+    visitor: &mut V,
+) {
+    let mut path = Vec::new();
 
     for child in &document.children {
         if matches!(&child.data, NodeData::Canvas { .. })
             && page_ids.contains(&child.id().to_string())
         {
-            traverse_node(child, visitor, 1, &mut path);
+            traverse_node(child, visitor, filter, 1, &mut path, false);
         }
     }
 }
 
-fn traverse_node<V: NodeVisitor>(
-    node: &Node,
+/// Walk a subtree, skipping hidden nodes and frames rejected by the frame pattern.
+///
+/// With a frame pattern set, only the outermost frame on each branch is matched;
+/// once it passes, its whole subtree is kept. Containers above frames (pages,
+/// sections, groups) are still walked, but loose content outside any frame is not.
+fn traverse_node<'a, V: NodeVisitor>(
+    node: &'a Node,
     visitor: &mut V,
+    filter: &FilterCriteria,
     depth: usize,
-    path: &mut Vec<String>,
+    path: &mut Vec<&'a Node>,
+    in_matched_frame: bool,
 ) {
+    if !filter.include_hidden && !node.is_visible() {
+        return;
+    }
+
+    let mut in_matched_frame = in_matched_frame;
+    if filter.frame_pattern.is_some() && !in_matched_frame {
+        if is_frame(node) {
+            if !filter.matches_frame(node.name()) {
+                return;
+            }
+            in_matched_frame = true;
+        } else if !is_frame_ancestor(node) {
+            return;
+        }
+    }
+
     visitor.visit_node(node, depth, path);
 
     if let Some(children) = node.children()
         && !children.is_empty()
     {
-        path.push(node.name().to_string());
+        path.push(node);
         for child in children {
-            traverse_node(child, visitor, depth + 1, path);
+            traverse_node(child, visitor, filter, depth + 1, path, in_matched_frame);
         }
         path.pop();
     }
 }
 
+pub(crate) const fn is_frame(node: &Node) -> bool {
+    matches!(
+        &node.data,
+        NodeData::Frame { .. }
+            | NodeData::Component { .. }
+            | NodeData::ComponentSet { .. }
+            | NodeData::Instance { .. }
+    )
+}
+
+const fn is_frame_ancestor(node: &Node) -> bool {
+    matches!(
+        &node.data,
+        NodeData::Canvas { .. } | NodeData::Section { .. } | NodeData::Group { .. }
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::document::{Color, NodeBase};
+    use crate::models::document::{Appearance, Color, Layout, NodeBase};
+    use std::collections::HashMap;
 
     struct CountingVisitor {
         count: usize,
     }
 
     impl NodeVisitor for CountingVisitor {
-        fn visit_node(&mut self, _node: &Node, _depth: usize, _path: &[String]) {
+        fn visit_node(&mut self, _node: &Node, _depth: usize, _path: &[&Node]) {
             self.count += 1;
         }
     }
@@ -71,7 +123,7 @@ mod tests {
         };
 
         let mut visitor = CountingVisitor { count: 0 };
-        traverse_document(&doc, &mut visitor);
+        traverse_document(&doc, &FilterCriteria::default(), &mut visitor);
 
         assert_eq!(visitor.count, 0);
     }
@@ -111,16 +163,167 @@ mod tests {
                             absolute_bounding_box: None,
                             fills: vec![],
                             clips_content: false,
+                            layout: Layout::default(),
+                            appearance: Appearance::default(),
                             children: vec![],
                         },
+                        extra: serde_json::Map::new(),
                     }],
                 },
+                extra: serde_json::Map::new(),
             }],
         };
 
         let mut visitor = CountingVisitor { count: 0 };
-        traverse_document(&doc, &mut visitor);
+        traverse_document(&doc, &FilterCriteria::default(), &mut visitor);
 
         assert_eq!(visitor.count, 2);
     }
+
+    #[derive(Default)]
+    struct IdCollector {
+        ids: Vec<String>,
+    }
+
+    impl NodeVisitor for IdCollector {
+        fn visit_node(&mut self, node: &Node, _depth: usize, _path: &[&Node]) {
+            self.ids.push(node.id().to_string());
+        }
+    }
+
+    fn node(node_type: &str, id: &str, name: &str, visible: bool, data: NodeData) -> Node {
+        Node {
+            base: NodeBase {
+                node_type: node_type.to_string(),
+                id: id.to_string(),
+                name: name.to_string(),
+                visible,
+                locked: false,
+            },
+            data,
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    fn frame(id: &str, name: &str, visible: bool, children: Vec<Node>) -> Node {
+        node(
+            "FRAME",
+            id,
+            name,
+            visible,
+            NodeData::Frame {
+                absolute_bounding_box: None,
+                fills: vec![],
+                clips_content: false,
+                layout: Layout::default(),
+                appearance: Appearance::default(),
+                children,
+            },
+        )
+    }
+
+    fn text(id: &str, visible: bool) -> Node {
+        node(
+            "TEXT",
+            id,
+            "Text",
+            visible,
+            NodeData::Text {
+                characters: "copy".to_string(),
+                absolute_bounding_box: None,
+                fills: vec![],
+                style: None,
+                character_style_overrides: vec![],
+                style_override_table: HashMap::new(),
+                appearance: Appearance::default(),
+            },
+        )
+    }
+
+    fn sample_document() -> Document {
+        let page = node(
+            "CANVAS",
+            "0:1",
+            "Page",
+            true,
+            NodeData::Canvas {
+                background_color: None,
+                export_settings: vec![],
+                children: vec![
+                    frame(
+                        "1:1",
+                        "Checkout",
+                        true,
+                        vec![text("1:2", true), frame("1:3", "Inner", true, vec![])],
+                    ),
+                    frame("2:1", "Settings", true, vec![text("2:2", true)]),
+                    frame("3:1", "Checkout Draft", false, vec![text("3:2", true)]),
+                    text("4:1", false),
+                    text("5:1", true),
+                ],
+            },
+        );
+
+        Document {
+            id: "0:0".to_string(),
+            name: "Doc".to_string(),
+            node_type: "DOCUMENT".to_string(),
+            scroll_behavior: None,
+            children: vec![page],
+        }
+    }
+
+    #[test]
+    fn test_hidden_nodes_skipped_by_default() {
+        let mut visitor = IdCollector::default();
+        traverse_document(&sample_document(), &FilterCriteria::default(), &mut visitor);
+
+        assert_eq!(
+            visitor.ids,
+            vec!["0:1", "1:1", "1:2", "1:3", "2:1", "2:2", "5:1"]
+        );
+    }
+
+    #[test]
+    fn test_include_hidden() {
+        let filter = FilterCriteria::new().with_include_hidden(true);
+        let mut visitor = IdCollector::default();
+        traverse_document(&sample_document(), &filter, &mut visitor);
+
+        assert!(visitor.ids.contains(&"3:2".to_string()));
+        assert!(visitor.ids.contains(&"4:1".to_string()));
+    }
+
+    #[test]
+    fn test_frame_pattern_prunes_top_level_frames() {
+        let filter =
+            FilterCriteria::new().with_frame_pattern(regex::Regex::new("^Checkout").unwrap());
+        let mut visitor = IdCollector::default();
+        traverse_document(&sample_document(), &filter, &mut visitor);
+
+        // Nested "Inner" frame is kept because its ancestor matched; loose text is dropped
+        assert_eq!(visitor.ids, vec!["0:1", "1:1", "1:2", "1:3"]);
+    }
+
+    #[test]
+    fn test_path_holds_ancestor_nodes() {
+        struct PathRecorder(Vec<(String, Vec<String>)>);
+
+        impl NodeVisitor for PathRecorder {
+            fn visit_node(&mut self, node: &Node, _depth: usize, path: &[&Node]) {
+                let ancestors = path.iter().map(|n| n.node_type_str().to_string()).collect();
+                self.0.push((node.id().to_string(), ancestors));
+            }
+        }
+
+        let mut visitor = PathRecorder(Vec::new());
+        traverse_document(&sample_document(), &FilterCriteria::default(), &mut visitor);
+
+        let (_, page_path) = &visitor.0[0];
+        assert!(page_path.is_empty());
+
+        let (id, text_path) = &visitor.0[2];
+        assert_eq!(id, "1:2");
+        assert_eq!(text_path, &vec!["CANVAS".to_string(), "FRAME".to_string()]);
+    }
 }
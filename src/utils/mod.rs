@@ -7,6 +7,7 @@ pub mod validation;
 
 // Re-export commonly used utilities
 pub use validation::{
-    parse_file_and_nodes_from_url, parse_file_key_from_url, parse_node_id_from_url,
-    parse_page_list, validate_file_key, validate_regex_pattern, validate_token,
+    parse_file_and_nodes_from_url, parse_file_key_from_url, parse_file_url, parse_node_id_from_url,
+    parse_page_list, parse_project_id, parse_team_id, validate_file_key, validate_regex_pattern,
+    validate_token,
 };
@@ -8,6 +8,7 @@ use crate::core::errors::{Error, Result};
 static FILE_KEY_REGEX: OnceLock<Regex> = OnceLock::new();
 static URL_REGEX: OnceLock<Regex> = OnceLock::new();
 static NODE_ID_REGEX: OnceLock<Regex> = OnceLock::new();
+static CONTAINER_REGEX: OnceLock<Regex> = OnceLock::new();
 
 fn file_key_regex() -> &'static Regex {
     FILE_KEY_REGEX.get_or_init(|| {
@@ -17,7 +18,7 @@ fn file_key_regex() -> &'static Regex {
 
 fn url_regex() -> &'static Regex {
     URL_REGEX.get_or_init(|| {
-        Regex::new(r"figma\.com/(?:file|design)/([a-zA-Z0-9]{22,})")
+        Regex::new(r"figma\.com/(?:file|design)/([a-zA-Z0-9]{22,})(?:/branch/([a-zA-Z0-9]{22,}))?")
             .expect("Failed to compile URL regex")
     })
 }
@@ -28,6 +29,12 @@ fn node_id_regex() -> &'static Regex {
     })
 }
 
+fn container_regex() -> &'static Regex {
+    CONTAINER_REGEX.get_or_init(|| {
+        Regex::new(r"/(team|project)/([0-9]+)").expect("Failed to compile team/project regex")
+    })
+}
+
 pub fn validate_file_key(file_key: &str) -> Result<()> {
     if file_key.is_empty() {
         return Err(Error::validation("file_key", "File key cannot be empty"));
@@ -45,14 +52,22 @@ pub fn validate_file_key(file_key: &str) -> Result<()> {
     Ok(())
 }
 
+/// File key from a URL or a bare key; a branch URL gives the branch's own key
 pub fn parse_file_key_from_url(input: &str) -> Result<String> {
+    let (file_key, branch_key) = parse_file_url(input)?;
+    Ok(branch_key.unwrap_or(file_key))
+}
+
+/// Main file key and, for `/branch/<key>` URLs, the branch key
+pub fn parse_file_url(input: &str) -> Result<(String, Option<String>)> {
     if input.contains("figma.com") {
         if let Some(captures) = url_regex().captures(input)
             && let Some(file_key) = captures.get(1)
         {
             let key = file_key.as_str().to_string();
             validate_file_key(&key)?;
-            return Ok(key);
+            let branch_key = captures.get(2).map(|b| b.as_str().to_string());
+            return Ok((key, branch_key));
         }
 
         return Err(Error::validation(
@@ -62,7 +77,7 @@ pub fn parse_file_key_from_url(input: &str) -> Result<String> {
     }
 
     validate_file_key(input)?;
-    Ok(input.to_string())
+    Ok((input.to_string(), None))
 }
 
 pub fn validate_token(token: &str) -> Result<()> {
@@ -122,6 +137,40 @@ pub fn parse_node_id_from_url(url: &str) -> Option<String> {
     None
 }
 
+/// Parse a team ID from a team URL or a bare numeric ID
+pub fn parse_team_id(input: &str) -> Result<String> {
+    parse_container_id(input, "team")
+}
+
+/// Parse a project ID from a project URL or a bare numeric ID
+pub fn parse_project_id(input: &str) -> Result<String> {
+    parse_container_id(input, "project")
+}
+
+fn parse_container_id(input: &str, kind: &'static str) -> Result<String> {
+    if input.contains("figma.com") {
+        return container_regex()
+            .captures_iter(input)
+            .find(|captures| &captures[1] == kind)
+            .map(|captures| captures[2].to_string())
+            .ok_or_else(|| {
+                Error::validation(
+                    "url",
+                    format!("Could not extract {kind} ID from URL: {input}"),
+                )
+            });
+    }
+
+    if input.is_empty() || !input.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::validation(
+            kind,
+            format!("Invalid {kind} ID '{input}'. Expected a numeric ID or a Figma {kind} URL"),
+        ));
+    }
+
+    Ok(input.to_string())
+}
+
 /// Parse file key and node IDs from a Figma URL
 pub fn parse_file_and_nodes_from_url(url: &str) -> Result<(String, Vec<String>)> {
     let file_key = parse_file_key_from_url(url)?;
@@ -172,6 +221,51 @@ mod tests {
         assert!(parse_file_key_from_url("https://figma.com/file/short").is_err());
     }
 
+    #[test]
+    fn test_parse_branch_url() {
+        let url = "https://www.figma.com/design/ABC123XYZ456789012345678/branch/BRANCH0123456789ABCDEFGH/Checkout?node-id=1-2";
+        assert_eq!(
+            parse_file_key_from_url(url).unwrap(),
+            "BRANCH0123456789ABCDEFGH"
+        );
+        assert_eq!(
+            parse_file_url(url).unwrap(),
+            (
+                "ABC123XYZ456789012345678".to_string(),
+                Some("BRANCH0123456789ABCDEFGH".to_string())
+            )
+        );
+        assert_eq!(
+            parse_file_and_nodes_from_url(url).unwrap(),
+            (
+                "BRANCH0123456789ABCDEFGH".to_string(),
+                vec!["1:2".to_string()]
+            )
+        );
+
+        let main = "https://www.figma.com/design/ABC123XYZ456789012345678/Checkout";
+        assert_eq!(
+            parse_file_url(main).unwrap(),
+            ("ABC123XYZ456789012345678".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_parse_team_and_project_id() {
+        assert_eq!(parse_team_id("1234567890").unwrap(), "1234567890");
+        assert_eq!(
+            parse_team_id("https://www.figma.com/files/team/1234567890/Acme").unwrap(),
+            "1234567890"
+        );
+        assert_eq!(
+            parse_project_id("https://www.figma.com/files/987/project/4567/Checkout").unwrap(),
+            "4567"
+        );
+
+        assert!(parse_team_id("acme").is_err());
+        assert!(parse_team_id("https://www.figma.com/files/project/4567").is_err());
+    }
+
     #[test]
     fn test_validate_token() {
         // Valid
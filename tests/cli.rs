@@ -0,0 +1,1861 @@
+//! End-to-end tests running the CLI binary against a mock Figma API
+
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::Value;
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+use tokio::process::Command;
+use wiremock::matchers::{body_json, body_string_contains, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const FILE_KEY: &str = "ABCDEFGHIJKLMNOPQRSTUV";
+const TOKEN: &str = "figd_test_token";
+
+fn fixture(name: &str) -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// Mock server plus an isolated home directory for config and cache
+struct Harness {
+    server: MockServer,
+    home: TempDir,
+}
+
+impl Harness {
+    async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+            home: TempDir::new().unwrap(),
+        }
+    }
+
+    async fn mount_file(&self) {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/files/{FILE_KEY}")))
+            .and(header("X-Figma-Token", TOKEN))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture("file.json")))
+            .mount(&self.server)
+            .await;
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let home = self.home.path();
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_figma-cli"));
+        cmd.args(args)
+            .current_dir(home)
+            .env("HOME", home)
+            .env("XDG_CONFIG_HOME", home.join("config"))
+            .env("XDG_CACHE_HOME", home.join("cache"))
+            .env("FIGMA_TOKEN", TOKEN)
+            .env_remove("RUST_LOG");
+        cmd
+    }
+
+    /// Run with `FIGMA_API_BASE` pointing at the mock server
+    async fn run(&self, args: &[&str]) -> Output {
+        self.command(args)
+            .env("FIGMA_API_BASE", self.server.uri())
+            .output()
+            .await
+            .unwrap()
+    }
+
+    fn read_json(&self, name: &str) -> Value {
+        let content = std::fs::read_to_string(self.home.path().join(name)).unwrap();
+        serde_json::from_str(&content).unwrap()
+    }
+}
+
+fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "command failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn stdout_json(output: &Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[tokio::test]
+async fn test_extract_against_mock_server() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let output = h.run(&["extract", FILE_KEY, "--output", "out.json"]).await;
+    assert_success(&output);
+
+    let result = h.read_json("out.json");
+    assert_eq!(result["metadata"]["fileKey"], FILE_KEY);
+    assert_eq!(result["metadata"]["version"], "4242");
+    assert_eq!(result["stats"]["totalPages"], 2);
+    assert_eq!(result["texts"].as_array().unwrap().len(), 4);
+    assert_eq!(result["texts"][0]["text"], "Your cart");
+}
+
+#[tokio::test]
+async fn test_extract_styled_runs() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let output = h.run(&["extract", FILE_KEY, "--output", "out.json"]).await;
+    assert_success(&output);
+
+    let result = h.read_json("out.json");
+    let text = &result["texts"][1];
+    assert_eq!(text["style"]["lineHeightPx"], 24.0);
+    assert_eq!(text["style"]["textAlignHorizontal"], "CENTER");
+
+    let runs = text["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0]["text"], "Proceed to ");
+    assert_eq!(runs[1]["text"], "payment");
+    assert_eq!(runs[1]["style"]["fontWeight"], 700);
+    assert_eq!(runs[1]["style"]["textDecoration"], "UNDERLINE");
+    assert_eq!(runs[1]["style"]["letterSpacing"], 0.5);
+    assert!(result["texts"][0].get("runs").is_none());
+}
+
+#[tokio::test]
+async fn test_extract_frame_pattern_and_hidden_nodes() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let output = h
+        .run(&[
+            "extract",
+            FILE_KEY,
+            "--frame-pattern",
+            "^Pay",
+            "--output",
+            "pattern.json",
+        ])
+        .await;
+    assert_success(&output);
+
+    let result = h.read_json("pattern.json");
+    let texts = result["texts"].as_array().unwrap();
+    assert_eq!(texts.len(), 1);
+    assert_eq!(texts[0]["text"], "Payment method");
+    assert_eq!(result["structure"]["pages"][0]["frameCount"], 1);
+    assert_eq!(result["structure"]["pages"][0]["textNodeCount"], 1);
+    assert_eq!(result["structure"]["pages"][1]["textNodeCount"], 0);
+
+    let output = h
+        .run(&[
+            "extract",
+            FILE_KEY,
+            "--include-hidden",
+            "--output",
+            "hidden.json",
+        ])
+        .await;
+    assert_success(&output);
+
+    let result = h.read_json("hidden.json");
+    assert_eq!(result["texts"].as_array().unwrap().len(), 5);
+    assert_eq!(result["structure"]["pages"][0]["textNodeCount"], 4);
+}
+
+#[tokio::test]
+async fn test_extract_with_metadata() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let output = h
+        .run(&[
+            "extract",
+            FILE_KEY,
+            "--with-metadata",
+            "--output",
+            "out.json",
+        ])
+        .await;
+    assert_success(&output);
+
+    let result = h.read_json("out.json");
+    let elements = result["elements"].as_array().unwrap();
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[0]["name"], "Cart");
+    assert_eq!(elements[0]["elementType"], "FRAME");
+    assert_eq!(elements[0]["childCount"], 2);
+    assert_eq!(elements[0]["dimensions"]["width"], 375.0);
+    assert_eq!(elements[0]["path"]["pageName"], "Mobile");
+}
+
+#[tokio::test]
+async fn test_extract_with_images() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/images/{FILE_KEY}")))
+        .and(query_param("ids", "1:1,1:4"))
+        .and(query_param("format", "png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "err": null,
+            "images": {
+                "1:1": format!("{}/renders/1-1.png", h.server.uri()),
+                "1:4": format!("{}/renders/1-4.png", h.server.uri())
+            }
+        })))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/renders/1-1.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"cart".to_vec()))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/renders/1-4.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"payment".to_vec()))
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&[
+            "extract",
+            FILE_KEY,
+            "--with-images",
+            "--image-dir",
+            "screens",
+            "--output",
+            "out.json",
+        ])
+        .await;
+    assert_success(&output);
+
+    let result = h.read_json("out.json");
+    assert_eq!(result["stats"]["totalImages"], 2);
+
+    let images = result["images"].as_array().unwrap();
+    assert_eq!(images[0]["nodeName"], "Cart");
+    assert_eq!(images[0]["dimensions"]["height"], 812.0);
+    assert_eq!(images[1]["localPath"], "screens/1-4.png");
+
+    let saved = std::fs::read(h.home.path().join("screens/1-4.png")).unwrap();
+    assert_eq!(saved, b"payment");
+}
+
+#[tokio::test]
+async fn test_extract_reads_base_url_from_config() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    std::fs::write(
+        h.home.path().join("figma-cli.toml"),
+        format!("[http]\nbase_url = \"{}\"\n", h.server.uri()),
+    )
+    .unwrap();
+
+    let output = h
+        .command(&["extract", FILE_KEY, "--output", "out.json"])
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+
+    assert_eq!(
+        h.read_json("out.json")["metadata"]["fileName"],
+        "Checkout Flow"
+    );
+}
+
+#[tokio::test]
+async fn test_base_url_with_api_version_is_rejected() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let output = h
+        .command(&["extract", FILE_KEY, "--output", "out.json"])
+        .env("FIGMA_API_BASE", format!("{}/v1/", h.server.uri()))
+        .output()
+        .await
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("base_url must not include the API version"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains(&format!("(try {})", h.server.uri())),
+        "{stderr}"
+    );
+}
+
+#[tokio::test]
+async fn test_extract_second_run_hits_cache() {
+    let h = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("file.json")))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+
+    assert_success(&h.run(&["extract", FILE_KEY, "--output", "a.json"]).await);
+    assert_success(&h.run(&["extract", FILE_KEY, "--output", "b.json"]).await);
+
+    assert_eq!(
+        h.read_json("a.json")["texts"],
+        h.read_json("b.json")["texts"]
+    );
+}
+
+#[tokio::test]
+async fn test_extract_at_version() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let mut signed_off = fixture("file.json");
+    signed_off["version"] = "1001".into();
+    signed_off["document"]["children"][0]["children"][0]["children"][0]["characters"] =
+        "Your basket".into();
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .and(query_param("version", "1001"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(signed_off))
+        .expect(1)
+        .with_priority(1)
+        .mount(&h.server)
+        .await;
+
+    // Latest first, so a version-blind cache would serve the wrong copy
+    assert_success(
+        &h.run(&["extract", FILE_KEY, "--output", "latest.json"])
+            .await,
+    );
+    for _ in 0..2 {
+        let output = h
+            .run(&[
+                "extract",
+                FILE_KEY,
+                "--at-version",
+                "1001",
+                "--output",
+                "pinned.json",
+            ])
+            .await;
+        assert_success(&output);
+
+        let result = h.read_json("pinned.json");
+        assert_eq!(result["metadata"]["version"], "1001");
+        assert_eq!(result["texts"][0]["text"], "Your basket");
+    }
+
+    assert_eq!(h.read_json("latest.json")["texts"][0]["text"], "Your cart");
+}
+
+#[tokio::test]
+async fn test_versions_follows_pagination() {
+    let h = Harness::start().await;
+    let version = |id: &str, label: Option<&str>| {
+        serde_json::json!({
+            "id": id,
+            "created_at": "2025-01-15T10:30:00Z",
+            "label": label,
+            "description": label.map(|_| "Approved by design review"),
+            "user": { "id": "7", "handle": "Ana", "img_url": "https://example.com/a.png" }
+        })
+    };
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}/versions")))
+        .and(query_param("before", "1002"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "versions": [version("1001", Some("Sign-off"))],
+            "pagination": {}
+        })))
+        .with_priority(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}/versions")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "versions": [version("1003", None), version("1002", None)],
+            "pagination": { "next_page": "https://api.figma.com/v1/files/x/versions?before=1002" }
+        })))
+        .mount(&h.server)
+        .await;
+
+    let output = h.run(&["versions", FILE_KEY]).await;
+    assert_success(&output);
+
+    let result = stdout_json(&output);
+    assert_eq!(result["total"], 3);
+    let versions = result["versions"].as_array().unwrap();
+    assert_eq!(versions[2]["id"], "1001");
+    assert_eq!(versions[2]["label"], "Sign-off");
+    assert_eq!(versions[2]["user"]["handle"], "Ana");
+
+    let output = h.run(&["versions", FILE_KEY, "--limit", "1"]).await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["versions"][0]["id"], "1003");
+}
+
+#[tokio::test]
+async fn test_diff_between_versions() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    // Older version: different title copy, CTA lived in Payment, no Notes page
+    let mut old = fixture("file.json");
+    old["version"] = "1001".into();
+    let mobile = &mut old["document"]["children"][0]["children"];
+    mobile[0]["children"][0]["characters"] = "Your basket".into();
+    let cta = mobile[0]["children"].as_array_mut().unwrap().remove(1);
+    mobile[1]["children"].as_array_mut().unwrap().push(cta);
+    mobile[1]["name"] = "Checkout".into();
+    old["document"]["children"].as_array_mut().unwrap().pop();
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .and(query_param("version", "1001"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&old))
+        .with_priority(1)
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&["diff", FILE_KEY, "--from", "1001", "--output", "diff.json"])
+        .await;
+    assert_success(&output);
+
+    let diff = h.read_json("diff.json");
+    assert_eq!(diff["from"]["version"], "1001");
+    assert_eq!(diff["to"]["version"], "4242");
+    assert_eq!(
+        diff["summary"],
+        serde_json::json!({
+            "added": 1, "removed": 0, "moved": 1, "renamed": 1,
+            "characters": 1, "fills": 0, "size": 0, "style": 0
+        })
+    );
+
+    let nodes = diff["nodes"].as_array().unwrap();
+    assert_eq!(nodes[0]["nodeId"], "1:2");
+    assert_eq!(nodes[0]["changes"][0]["kind"], "characters");
+    assert_eq!(nodes[1]["nodeId"], "1:3");
+    assert_eq!(nodes[1]["changes"][0]["kind"], "moved");
+    assert_eq!(nodes[1]["changes"][0]["from"]["frameNames"][0], "Checkout");
+    let added = nodes.iter().find(|n| n["status"] == "added").unwrap();
+    assert_eq!(added["name"], "Notes");
+    assert_eq!(added["descendants"], 1);
+
+    // Snapshots need no API access at all
+    std::fs::write(
+        h.home.path().join("old.json"),
+        serde_json::to_string(&old).unwrap(),
+    )
+    .unwrap();
+    std::fs::write(
+        h.home.path().join("new.json"),
+        serde_json::to_string(&fixture("file.json")).unwrap(),
+    )
+    .unwrap();
+    let output = h
+        .command(&[
+            "diff",
+            "--from-snapshot",
+            "old.json",
+            "--to-snapshot",
+            "new.json",
+            "--format",
+            "text",
+        ])
+        .env_remove("FIGMA_TOKEN")
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("--- Checkout Flow @ 1001"));
+    assert!(text.contains("@@ Mobile > Cart > Title (1:2) @@\n-Your basket\n+Your cart"));
+    assert!(text.contains("-name: Checkout\n+name: Payment"));
+}
+
+#[tokio::test]
+async fn test_comments_list_threads_and_filters() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let comment = |id: &str, parent: &str, handle: &str, created: &str, node: Option<&str>| {
+        serde_json::json!({
+            "id": id,
+            "parent_id": parent,
+            "file_key": FILE_KEY,
+            "user": { "id": handle, "handle": handle, "img_url": "" },
+            "message": format!("Comment {id}, please check"),
+            "created_at": created,
+            "resolved_at": if id == "3" { Some("2025-01-13T09:00:00Z") } else { None },
+            "client_meta": node.map(|n| serde_json::json!({ "node_id": n, "node_offset": { "x": 0, "y": 0 } })),
+            "order_id": id
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}/comments")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "comments": [
+                comment("2", "1", "ben", "2025-01-11T09:00:00Z", None),
+                comment("1", "", "ana", "2025-01-10T09:00:00Z", Some("1:2")),
+                comment("3", "", "ben", "2025-01-12T09:00:00Z", Some("1:5")),
+            ]
+        })))
+        .mount(&h.server)
+        .await;
+
+    let output = h.run(&["comments", "list", FILE_KEY]).await;
+    assert_success(&output);
+
+    let result = stdout_json(&output);
+    let threads = result["threads"].as_array().unwrap();
+    assert_eq!(threads.len(), 2);
+    assert_eq!(threads[0]["anchor"]["name"], "Title");
+    assert_eq!(threads[0]["anchor"]["path"]["pageName"], "Mobile");
+    assert_eq!(threads[0]["replies"][0]["author"], "ben");
+
+    let output = h
+        .run(&["comments", "list", FILE_KEY, "--status", "open"])
+        .await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["threads"].as_array().unwrap().len(), 1);
+
+    let output = h
+        .run(&[
+            "comments",
+            "list",
+            FILE_KEY,
+            "--author",
+            "BEN",
+            "--since",
+            "2025-01-12",
+            "--format",
+            "csv",
+        ])
+        .await;
+    assert_success(&output);
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<&str> = text.lines().collect();
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].starts_with("thread_id,comment_id,parent_id,author"));
+    assert!(rows[1].contains(",1:5,Heading,Mobile > Payment,\"Comment 3, please check\""));
+}
+
+#[tokio::test]
+async fn test_comments_post_reply_and_delete() {
+    let h = Harness::start().await;
+    let created = |id: &str, parent: &str| {
+        serde_json::json!({
+            "id": id,
+            "parent_id": parent,
+            "user": { "id": "9", "handle": "lint-bot" },
+            "message": "Copy lint failed",
+            "created_at": "2025-01-15T10:30:00Z",
+            "resolved_at": null
+        })
+    };
+
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/files/{FILE_KEY}/comments")))
+        .and(header("X-Figma-Token", TOKEN))
+        .and(body_json(serde_json::json!({
+            "message": "Copy lint failed",
+            "client_meta": { "node_id": "1:2", "node_offset": { "x": 0.0, "y": 0.0 } }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(created("10", "")))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/files/{FILE_KEY}/comments")))
+        .and(body_json(serde_json::json!({
+            "message": "Fixed",
+            "comment_id": "10"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(created("11", "10")))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("/v1/files/{FILE_KEY}/comments/11")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+
+    let url = format!("https://www.figma.com/design/{FILE_KEY}/Checkout?node-id=1-2");
+    let output = h
+        .run(&["comments", "post", &url, "--message", "Copy lint failed"])
+        .await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["id"], "10");
+
+    let output = h
+        .run(&["comments", "reply", FILE_KEY, "10", "-m", "Fixed"])
+        .await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["parent_id"], "10");
+
+    let output = h.run(&["comments", "delete", FILE_KEY, "11"]).await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["deleted"], true);
+
+    // Dry runs print the request and never reach the server
+    let output = h
+        .run(&[
+            "comments",
+            "post",
+            FILE_KEY,
+            "--node",
+            "1-3",
+            "-m",
+            "Token drift",
+            "--dry-run",
+        ])
+        .await;
+    assert_success(&output);
+    let request = stdout_json(&output);
+    assert_eq!(request["method"], "POST");
+    assert_eq!(
+        request["url"],
+        format!("{}/v1/files/{FILE_KEY}/comments", h.server.uri())
+    );
+    assert_eq!(request["body"]["client_meta"]["node_id"], "1:3");
+
+    let output = h
+        .run(&["comments", "delete", FILE_KEY, "11", "--dry-run"])
+        .await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["method"], "DELETE");
+}
+
+#[tokio::test]
+async fn test_projects_and_files_use_configured_team() {
+    let h = Harness::start().await;
+    std::fs::write(h.home.path().join("figma-cli.toml"), "team = \"42\"\n").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/teams/42/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "Acme",
+            "projects": [
+                { "id": "100", "name": "Design System" },
+                { "id": "200", "name": "Checkout" }
+            ]
+        })))
+        .mount(&h.server)
+        .await;
+    let project = |name: &str, key: &str, modified: &str| {
+        serde_json::json!({
+            "name": name,
+            "files": [{
+                "key": key,
+                "name": format!("{name} file"),
+                "thumbnail_url": format!("https://example.com/{key}.png"),
+                "last_modified": modified
+            }]
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/100/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(project(
+            "Design System",
+            "KEY100",
+            "2025-01-15T10:30:00Z",
+        )))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/200/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(project(
+            "Checkout",
+            "KEY200",
+            "2025-02-01T08:00:00Z",
+        )))
+        .mount(&h.server)
+        .await;
+
+    let output = h.run(&["projects"]).await;
+    assert_success(&output);
+    let result = stdout_json(&output);
+    assert_eq!(result["teamName"], "Acme");
+    assert_eq!(result["total"], 2);
+
+    let output = h.run(&["projects", "--format", "table"]).await;
+    assert_success(&output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.starts_with("ID   NAME\n100  Design System\n"));
+
+    // No project: every project of the team
+    let output = h.run(&["files"]).await;
+    assert_success(&output);
+    let result = stdout_json(&output);
+    assert_eq!(result["total"], 2);
+    assert_eq!(result["files"][1]["key"], "KEY200");
+    assert_eq!(result["files"][1]["projectName"], "Checkout");
+    assert_eq!(result["files"][1]["lastModified"], "2025-02-01T08:00:00Z");
+    assert_eq!(
+        result["files"][1]["thumbnailUrl"],
+        "https://example.com/KEY200.png"
+    );
+
+    let output = h
+        .run(&[
+            "files",
+            "https://www.figma.com/files/project/100/Design-System",
+        ])
+        .await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["files"][0]["key"], "KEY100");
+}
+
+#[tokio::test]
+async fn test_extract_batch_reports_each_file() {
+    const MISSING_KEY: &str = "ZYXWVUTSRQPONMLKJIHGFE";
+
+    let h = Harness::start().await;
+    h.mount_file().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{MISSING_KEY}")))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&h.server)
+        .await;
+
+    let manifest = format!(
+        r#"
+[[files]]
+file = "{FILE_KEY}"
+pages = ["Mobile"]
+format = "markdown"
+output = "out/checkout.md"
+
+[[files]]
+file = "{MISSING_KEY}"
+"#
+    );
+    std::fs::write(h.home.path().join("batch.toml"), manifest).unwrap();
+
+    let output = h
+        .run(&["extract-batch", "batch.toml", "--concurrency", "2"])
+        .await;
+    assert!(!output.status.success());
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains(&format!("✓ {FILE_KEY} → out/checkout.md")));
+    assert!(text.contains(&format!("✗ {MISSING_KEY}")));
+    assert!(text.contains("Extracted 1 of 2 files"));
+
+    let markdown = std::fs::read_to_string(h.home.path().join("out/checkout.md")).unwrap();
+    assert!(markdown.contains("Your cart"));
+
+    // Every file of a project, named after its key
+    Mock::given(method("GET"))
+        .and(path("/v1/projects/100/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "Checkout",
+            "files": [{
+                "key": FILE_KEY,
+                "name": "Checkout Flow",
+                "last_modified": "2025-01-15T10:30:00Z"
+            }]
+        })))
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&[
+            "extract-batch",
+            "--project",
+            "100",
+            "--output-dir",
+            "project",
+        ])
+        .await;
+    assert_success(&output);
+
+    let result = h.read_json(&format!("project/{FILE_KEY}.json"));
+    assert_eq!(result["metadata"]["fileKey"], FILE_KEY);
+}
+
+#[tokio::test]
+async fn test_watch_reextracts_on_new_version() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let meta = |version: &str, modified: &str| {
+        let mut file = fixture("file.json");
+        file["version"] = version.into();
+        file["lastModified"] = modified.into();
+        ResponseTemplate::new(200).set_body_json(file)
+    };
+    let poll = || {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/files/{FILE_KEY}")))
+            .and(query_param("depth", "1"))
+    };
+    // Too long a Retry-After for the client to wait out, so watch backs off itself
+    poll()
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&h.server)
+        .await;
+    poll()
+        .respond_with(meta("1", "2025-01-15T10:30:00Z"))
+        .up_to_n_times(1)
+        .with_priority(2)
+        .mount(&h.server)
+        .await;
+    poll()
+        .respond_with(meta("2", "2025-01-15T11:00:00Z"))
+        .with_priority(3)
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&[
+            "watch",
+            FILE_KEY,
+            "--output",
+            "copy.json",
+            "--interval",
+            "1",
+            "--max-runs",
+            "2",
+            "--exec",
+            "echo $FIGMA_VERSION >> hook.log",
+        ])
+        .await;
+    assert_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("✓ Version 1 (2025-01-15 10:30:00 UTC) → copy.json"));
+    assert!(stdout.contains("✓ Version 2 (2025-01-15 11:00:00 UTC) → copy.json"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Rate limited, next check in 2s"));
+
+    let hook = std::fs::read_to_string(h.home.path().join("hook.log")).unwrap();
+    assert_eq!(hook, "1\n2\n");
+    assert_eq!(h.read_json("copy.json")["metadata"]["fileKey"], FILE_KEY);
+}
+
+#[tokio::test]
+async fn test_watch_reextracts_edit_without_new_version() {
+    let h = Harness::start().await;
+
+    let file = |text: &str, modified: &str| {
+        let body = fixture("file.json").to_string().replace("Your cart", text);
+        let mut file: Value = serde_json::from_str(&body).unwrap();
+        file["version"] = "1".into();
+        file["lastModified"] = modified.into();
+        ResponseTemplate::new(200).set_body_json(file)
+    };
+    let request = |polling: bool| {
+        let mock = Mock::given(method("GET")).and(path(format!("/v1/files/{FILE_KEY}")));
+        if polling {
+            mock.and(query_param("depth", "1"))
+        } else {
+            mock.and(wiremock::matchers::query_param_is_missing("depth"))
+        }
+    };
+    // Same version both times; only lastModified and the content move
+    for (polling, first, second) in [
+        (
+            true,
+            file("Your cart", "2025-01-15T10:30:00Z"),
+            file("Your basket", "2025-01-15T11:00:00Z"),
+        ),
+        (
+            false,
+            file("Your cart", "2025-01-15T10:30:00Z"),
+            file("Your basket", "2025-01-15T11:00:00Z"),
+        ),
+    ] {
+        request(polling)
+            .respond_with(first)
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&h.server)
+            .await;
+        request(polling)
+            .respond_with(second)
+            .with_priority(2)
+            .mount(&h.server)
+            .await;
+    }
+
+    let output = h
+        .run(&[
+            "watch",
+            FILE_KEY,
+            "--output",
+            "copy.json",
+            "--interval",
+            "1",
+            "--max-runs",
+            "2",
+            "--exec",
+            "echo $FIGMA_VERSION >> hook.log",
+        ])
+        .await;
+    assert_success(&output);
+
+    assert_eq!(h.read_json("copy.json")["texts"][0]["text"], "Your basket");
+    let hook = std::fs::read_to_string(h.home.path().join("hook.log")).unwrap();
+    assert_eq!(hook, "1\n1\n");
+}
+
+#[tokio::test]
+async fn test_serve_webhooks_extracts_on_version_update() {
+    let h = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("file.json")))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+        .to_string();
+    let mut server = h
+        .command(&[
+            "serve-webhooks",
+            "--port",
+            &port,
+            "--passcode",
+            "secret",
+            "--debounce",
+            "1",
+            "--output-dir",
+            "out",
+            "--exec",
+            "echo $FIGMA_EVENT $FIGMA_FILE_KEY >> hook.log",
+        ])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+
+    let url = format!("http://127.0.0.1:{port}/");
+    let http = reqwest::Client::new();
+    let mut ready = false;
+    for _ in 0..100 {
+        if http.get(&url).send().await.is_ok() {
+            ready = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert!(ready, "receiver did not start");
+
+    let event = |passcode: &str, version: &str| {
+        serde_json::json!({
+            "event_type": "FILE_VERSION_UPDATE",
+            "passcode": passcode,
+            "timestamp": "2025-01-15T10:30:00Z",
+            "webhook_id": "22",
+            "file_key": FILE_KEY,
+            "file_name": "Checkout Flow",
+            "version_id": version
+        })
+    };
+
+    let rejected = http
+        .post(&url)
+        .json(&event("wrong", "1"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), 403);
+
+    // Two quick versions collapse into one extraction
+    for version in ["1", "2"] {
+        let accepted = http
+            .post(&url)
+            .json(&event("secret", version))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), 200);
+    }
+
+    let hook = h.home.path().join("hook.log");
+    for _ in 0..100 {
+        if hook.exists() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    server.kill().await.unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&hook).unwrap(),
+        format!("FILE_VERSION_UPDATE {FILE_KEY}\n")
+    );
+    let result = h.read_json(&format!("out/{FILE_KEY}.json"));
+    assert_eq!(result["metadata"]["fileKey"], FILE_KEY);
+
+    let log = std::fs::read_to_string(h.home.path().join("figma-webhooks.jsonl")).unwrap();
+    let records: Vec<Value> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let statuses: Vec<&str> = records
+        .iter()
+        .map(|r| r["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["rejected", "accepted", "accepted", "extracted"]);
+    assert!(!log.contains("secret"));
+    assert_eq!(records[2]["event"]["version_id"], "2");
+}
+
+#[tokio::test]
+async fn test_webhooks_create_list_and_delete() {
+    let h = Harness::start().await;
+    let webhook = serde_json::json!({
+        "id": "22",
+        "event_type": "FILE_VERSION_UPDATE",
+        "context": "file",
+        "context_id": FILE_KEY,
+        "endpoint": "https://example.com/hooks",
+        "status": "PAUSED",
+        "description": "extract on publish"
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/v2/webhooks"))
+        .and(header("X-Figma-Token", TOKEN))
+        .and(body_json(serde_json::json!({
+            "event_type": "FILE_VERSION_UPDATE",
+            "context": "file",
+            "context_id": FILE_KEY,
+            "endpoint": "https://example.com/hooks",
+            "passcode": "secret",
+            "status": "PAUSED",
+            "description": "extract on publish"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&webhook))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v2/webhooks"))
+        .and(query_param("context", "team"))
+        .and(query_param("context_id", "1234"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "webhooks": [webhook] })),
+        )
+        .expect(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v2/webhooks/22"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&[
+            "webhooks",
+            "create",
+            "--event",
+            "file-version-update",
+            "--file",
+            FILE_KEY,
+            "--endpoint",
+            "https://example.com/hooks",
+            "--passcode",
+            "secret",
+            "--description",
+            "extract on publish",
+            "--paused",
+        ])
+        .await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["id"], "22");
+
+    let output = h
+        .run(&[
+            "webhooks",
+            "list",
+            "--team",
+            "https://www.figma.com/files/team/1234/Design",
+        ])
+        .await;
+    assert_success(&output);
+    let listing = stdout_json(&output);
+    assert_eq!(listing["context"], "team");
+    assert_eq!(listing["total"], 1);
+    assert_eq!(listing["webhooks"][0]["status"], "PAUSED");
+
+    let output = h.run(&["webhooks", "delete", "22"]).await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["deleted"], true);
+}
+
+#[tokio::test]
+async fn test_inspect_against_mock_server() {
+    let h = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}/nodes")))
+        .and(query_param("ids", "1:1"))
+        .and(query_param("depth", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("nodes.json")))
+        .mount(&h.server)
+        .await;
+
+    let output = h.run(&["inspect", FILE_KEY, "--nodes", "1-1"]).await;
+    assert_success(&output);
+
+    let result = stdout_json(&output);
+    assert_eq!(result["file"]["name"], "Checkout Flow");
+    assert_eq!(result["nodes"]["1:1"]["document"]["name"], "Cart");
+}
+
+#[tokio::test]
+async fn test_images_against_mock_server() {
+    let h = Harness::start().await;
+    let render_url = format!("{}/renders/1-1.png", h.server.uri());
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/images/{FILE_KEY}")))
+        .and(query_param("ids", "1:1"))
+        .and(query_param("format", "png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "err": null,
+            "images": { "1:1": render_url }
+        })))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/renders/1-1.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"\x89PNG fake".to_vec()))
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&["images", FILE_KEY, "--frames", "1:1", "--base64"])
+        .await;
+    assert_success(&output);
+
+    let result = stdout_json(&output);
+    assert_eq!(result["total"], 1);
+    assert_eq!(result["images"][0]["id"], "1:1");
+    assert_eq!(
+        result["images"][0]["data"],
+        general_purpose::STANDARD.encode(b"\x89PNG fake")
+    );
+}
+
+#[tokio::test]
+async fn test_images_fills_downloads_originals_with_manifest() {
+    let h = Harness::start().await;
+    let image = |image_ref: &str| serde_json::json!({ "type": "IMAGE", "imageRef": image_ref });
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "Campaign",
+            "version": "42",
+            "lastModified": "2025-01-15T10:30:00Z",
+            "editorType": "figma",
+            "document": {
+                "id": "0:0", "name": "Document", "type": "DOCUMENT",
+                "children": [{
+                    "id": "0:1", "name": "Launch", "type": "CANVAS",
+                    "children": [{
+                        "id": "1:1", "name": "Hero", "type": "FRAME", "fills": [image("photo")],
+                        "children": [
+                            { "id": "1:2", "name": "Portrait", "type": "RECTANGLE", "fills": [image("photo")] }
+                        ]
+                    }]
+                }]
+            }
+        })))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}/images")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "error": false,
+            "status": 200,
+            "meta": { "images": {
+                "photo": format!("{}/originals/photo", h.server.uri()),
+                "unused": format!("{}/originals/unused", h.server.uri())
+            } }
+        })))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/originals/photo"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"\x89PNG\r\n\x1a\nphoto".to_vec()))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/originals/unused"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0xFF, 0xD8, 0xFF, 0xE0]))
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&["images", FILE_KEY, "--fills", "--image-dir", "fills"])
+        .await;
+    assert_success(&output);
+
+    let manifest = h.read_json("fills/manifest.json");
+    assert_eq!(manifest["fileName"], "Campaign");
+    assert_eq!(manifest["total"], 2);
+
+    let photo = &manifest["images"][0];
+    assert_eq!(photo["imageRef"], "photo");
+    assert_eq!(photo["nodes"][0]["nodeId"], "1:1");
+    assert_eq!(photo["nodes"][1]["nodeId"], "1:2");
+    assert_eq!(photo["nodes"][1]["path"]["frameNames"][0], "Hero");
+    let local = photo["localPath"].as_str().unwrap();
+    assert!(local.ends_with(".png"));
+    assert_eq!(
+        std::fs::read(h.home.path().join(local)).unwrap(),
+        b"\x89PNG\r\n\x1a\nphoto"
+    );
+
+    // Images no node uses are still downloaded
+    assert_eq!(manifest["images"][1]["imageRef"], "unused");
+    assert_eq!(manifest["images"][1]["nodes"], serde_json::json!([]));
+    assert!(
+        manifest["images"][1]["localPath"]
+            .as_str()
+            .unwrap()
+            .ends_with(".jpg")
+    );
+}
+
+#[tokio::test]
+async fn test_branches_listed_and_extracted_by_url() {
+    const BRANCH_KEY: &str = "BRANCH0123456789ABCDEFGH";
+    let h = Harness::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .and(query_param("branch_data", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "Checkout",
+            "version": "1",
+            "lastModified": "2025-01-15T10:30:00Z",
+            "document": { "id": "0:0", "name": "Document", "type": "DOCUMENT", "children": [] },
+            "branches": [{
+                "key": BRANCH_KEY,
+                "name": "New onboarding",
+                "thumbnail_url": "https://example.com/branch.png",
+                "last_modified": "2025-02-01T08:00:00Z",
+                "link_url": format!("https://www.figma.com/design/{FILE_KEY}/branch/{BRANCH_KEY}")
+            }]
+        })))
+        .mount(&h.server)
+        .await;
+    for key in [FILE_KEY, BRANCH_KEY] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/files/{key}")))
+            .and(query_param("branch_data", "false"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture("file.json")))
+            .expect(1)
+            .mount(&h.server)
+            .await;
+    }
+
+    let branch_url =
+        format!("https://www.figma.com/design/{FILE_KEY}/branch/{BRANCH_KEY}/Checkout");
+
+    // A branch URL lists the branches of its main file
+    let output = h.run(&["branches", &branch_url]).await;
+    assert_success(&output);
+    let listing = stdout_json(&output);
+    assert_eq!(listing["fileKey"], FILE_KEY);
+    assert_eq!(listing["total"], 1);
+    assert_eq!(listing["branches"][0]["key"], BRANCH_KEY);
+    assert_eq!(listing["branches"][0]["name"], "New onboarding");
+
+    let output = h.run(&["branches", FILE_KEY, "--format", "table"]).await;
+    assert_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("New onboarding"));
+
+    // The branch and the main file are fetched and cached separately
+    for (file, out) in [
+        (branch_url.as_str(), "branch.json"),
+        (FILE_KEY, "main.json"),
+    ] {
+        let output = h.run(&["extract", file, "--output", out]).await;
+        assert_success(&output);
+    }
+    assert_eq!(
+        h.read_json("branch.json")["metadata"]["fileKey"],
+        BRANCH_KEY
+    );
+    assert_eq!(h.read_json("main.json")["metadata"]["fileKey"], FILE_KEY);
+
+    let output = h
+        .run(&["extract", &branch_url, "--output", "again.json"])
+        .await;
+    assert_success(&output);
+
+    let output = h.run(&["cache", "list", "--json"]).await;
+    let mut keys: Vec<String> = stdout_json(&output)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["file_key"].as_str().unwrap().to_string())
+        .collect();
+    keys.sort();
+    assert_eq!(keys, [FILE_KEY, BRANCH_KEY]);
+}
+
+#[tokio::test]
+async fn test_query_against_mock_server() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let output = h
+        .run(&["query", FILE_KEY, "document.children[*].name"])
+        .await;
+    assert_success(&output);
+
+    assert_eq!(stdout_json(&output), serde_json::json!(["Mobile", "Notes"]));
+}
+
+#[tokio::test]
+async fn test_query_exposes_auto_layout() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let output = h
+        .run(&[
+            "query",
+            FILE_KEY,
+            "document.children[0].children[0].{mode: layoutMode, gap: itemSpacing, top: paddingTop, constraints: constraints}",
+        ])
+        .await;
+    assert_success(&output);
+
+    assert_eq!(
+        stdout_json(&output),
+        serde_json::json!({
+            "mode": "VERTICAL",
+            "gap": 16.0,
+            "top": 24.0,
+            "constraints": { "vertical": "TOP", "horizontal": "CENTER" }
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_query_sees_unmodeled_fields() {
+    let h = Harness::start().await;
+    h.mount_file().await;
+
+    let expression = "document.children[0].children[0].{status: devStatus.type, spacing: boundVariables.itemSpacing.id}";
+    let expected = serde_json::json!({ "status": "READY_FOR_DEV", "spacing": "VariableID:1:2" });
+
+    // Second run is served from the cache, which must be lossless too
+    for _ in 0..2 {
+        let output = h.run(&["query", FILE_KEY, expression]).await;
+        assert_success(&output);
+        assert_eq!(stdout_json(&output), expected);
+    }
+}
+
+#[tokio::test]
+async fn test_api_errors_surface_as_failure() {
+    let h = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&h.server)
+        .await;
+
+    let output = h.run(&["query", FILE_KEY, "name"]).await;
+    assert!(!output.status.success());
+}
+
+#[tokio::test]
+async fn test_rate_limited_request_waits_for_retry_after() {
+    let h = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "1")
+                .insert_header("X-Figma-Rate-Limit-Type", "low"),
+        )
+        .up_to_n_times(1)
+        .mount(&h.server)
+        .await;
+    h.mount_file().await;
+
+    let started = std::time::Instant::now();
+    let output = h
+        .run(&["extract", FILE_KEY, "--output", "out.json", "--verbose"])
+        .await;
+    assert_success(&output);
+    assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+    assert_eq!(h.read_json("out.json")["metadata"]["fileKey"], FILE_KEY);
+
+    let log = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(log.contains("limit type: low"), "{log}");
+    assert!(log.contains("tier 1: 2"), "{log}");
+    assert!(log.contains("rate limited: 1, retries: 1"), "{log}");
+}
+
+#[tokio::test]
+async fn test_oauth_login_and_refresh_on_401() {
+    let h = Harness::start().await;
+    let basic = format!(
+        "Basic {}",
+        general_purpose::STANDARD.encode("app-id:app-secret")
+    );
+
+    Mock::given(method("POST"))
+        .and(path("/v1/oauth/token"))
+        .and(header("Authorization", basic.as_str()))
+        .and(body_string_contains("grant_type=authorization_code"))
+        .and(body_string_contains("code=granted"))
+        .and(body_string_contains("code_verifier="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "user_id_string": "7",
+            "access_token": "figu_first",
+            "refresh_token": "figur_refresh",
+            "expires_in": 7_776_000
+        })))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me"))
+        .and(header("Authorization", "Bearer figu_first"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "7",
+            "email": "ana@example.com",
+            "handle": "Ana"
+        })))
+        .mount(&h.server)
+        .await;
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+        .to_string();
+    let mut login = h
+        .command(&[
+            "auth",
+            "login",
+            "--oauth",
+            "--client-id",
+            "app-id",
+            "--client-secret",
+            "app-secret",
+            "--port",
+            &port,
+            "--no-browser",
+        ])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .env_remove("FIGMA_TOKEN")
+        .stdout(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+
+    // The authorization URL carries the state the redirect has to echo
+    let mut lines =
+        tokio::io::AsyncBufReadExt::lines(tokio::io::BufReader::new(login.stdout.take().unwrap()));
+    let authorize = loop {
+        let line = lines
+            .next_line()
+            .await
+            .unwrap()
+            .expect("no authorization URL");
+        if let Some(url) = line.trim().strip_prefix("https://www.figma.com/oauth?") {
+            break url.to_string();
+        }
+    };
+    let params: std::collections::HashMap<String, String> =
+        url::form_urlencoded::parse(authorize.as_bytes())
+            .into_owned()
+            .collect();
+    assert_eq!(params["client_id"], "app-id");
+    assert_eq!(params["code_challenge_method"], "S256");
+    assert_eq!(
+        params["redirect_uri"],
+        format!("http://localhost:{port}/callback")
+    );
+
+    let redirect = reqwest::get(format!(
+        "http://127.0.0.1:{port}/callback?code=granted&state={}",
+        params["state"]
+    ))
+    .await
+    .unwrap();
+    assert_eq!(redirect.status(), 200);
+    assert!(login.wait().await.unwrap().success());
+
+    // The stored access token is rejected once and refreshed
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .and(header("Authorization", "Bearer figu_first"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/oauth/refresh"))
+        .and(header("Authorization", basic.as_str()))
+        .and(body_string_contains("refresh_token=figur_refresh"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "figu_second",
+            "expires_in": 7_776_000
+        })))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .and(header("Authorization", "Bearer figu_second"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("file.json")))
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .command(&["extract", FILE_KEY, "--output", "out.json"])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .env_remove("FIGMA_TOKEN")
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+    assert_eq!(h.read_json("out.json")["metadata"]["fileKey"], FILE_KEY);
+
+    let credentials =
+        std::fs::read_to_string(h.home.path().join("config/figma-cli/credentials.toml")).unwrap();
+    assert!(credentials.contains("access_token = \"figu_second\""));
+    assert!(credentials.contains("refresh_token = \"figur_refresh\""));
+}
+
+#[tokio::test]
+async fn test_images_refreshes_expired_oauth_token() {
+    let h = Harness::start().await;
+    let config_dir = h.home.path().join("config/figma-cli");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    let credentials = config_dir.join("credentials.toml");
+    std::fs::write(
+        &credentials,
+        r#"[profiles.default.oauth]
+access_token = "figu_expired"
+refresh_token = "figur_refresh"
+expires_at = "2020-01-01T00:00:00Z"
+client_id = "app-id"
+client_secret = "app-secret"
+"#,
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&credentials, std::fs::Permissions::from_mode(0o600)).unwrap();
+    }
+
+    Mock::given(method("POST"))
+        .and(path("/v1/oauth/refresh"))
+        .and(body_string_contains("refresh_token=figur_refresh"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "figu_fresh",
+            "expires_in": 7_776_000
+        })))
+        .expect(1)
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/images/{FILE_KEY}")))
+        .and(header("Authorization", "Bearer figu_fresh"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "err": null,
+            "images": { "1:1": "https://example.com/1-1.png" }
+        })))
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .command(&["images", FILE_KEY, "--frames", "1:1"])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .env_remove("FIGMA_TOKEN")
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+    assert_eq!(
+        stdout_json(&output)["images"][0]["url"],
+        "https://example.com/1-1.png"
+    );
+    assert!(
+        std::fs::read_to_string(&credentials)
+            .unwrap()
+            .contains("access_token = \"figu_fresh\"")
+    );
+}
+
+#[tokio::test]
+async fn test_profiles_keep_tokens_teams_and_caches_apart() {
+    let h = Harness::start().await;
+    // Stored logins only; FIGMA_TOKEN would override every profile
+    let run = |args: &[&str]| {
+        let mut cmd = h.command(args);
+        cmd.env("FIGMA_API_BASE", h.server.uri())
+            .env_remove("FIGMA_TOKEN");
+        cmd
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/v1/me"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "7",
+            "email": "ana@example.com",
+            "handle": "Ana"
+        })))
+        .mount(&h.server)
+        .await;
+    for (token, team, name) in [
+        ("figd_client_a", "11", "Client A"),
+        ("figd_client_b", "22", "Client B"),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/teams/{team}/projects")))
+            .and(header("X-Figma-Token", token))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": name,
+                "projects": []
+            })))
+            .mount(&h.server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/files/{FILE_KEY}")))
+        .and(header("X-Figma-Token", "figd_client_a"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fixture("file.json")))
+        .mount(&h.server)
+        .await;
+
+    for (profile, token, team) in [
+        ("client-a", "figd_client_a", "11"),
+        ("client-b", "figd_client_b", "22"),
+    ] {
+        let output = run(&["auth", "login", token, "--profile", profile, "--team", team])
+            .output()
+            .await
+            .unwrap();
+        assert_success(&output);
+    }
+
+    let output = run(&["auth", "use", "client-b"]).output().await.unwrap();
+    assert_success(&output);
+
+    let output = run(&["auth", "list", "--json"]).output().await.unwrap();
+    assert_success(&output);
+    let profiles = stdout_json(&output);
+    assert_eq!(profiles[0]["name"], "client-a");
+    assert_eq!(profiles[0]["active"], false);
+    assert_eq!(profiles[1]["name"], "client-b");
+    assert_eq!(profiles[1]["active"], true);
+    assert_eq!(profiles[1]["kind"], "token");
+    assert_eq!(profiles[1]["team"], "22");
+
+    // The active profile's token and team
+    let output = run(&["projects"]).output().await.unwrap();
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["teamName"], "Client B");
+
+    // --profile overrides it for one command
+    let output = run(&["projects", "--profile", "client-a"])
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["teamName"], "Client A");
+
+    let output = run(&[
+        "extract",
+        FILE_KEY,
+        "--profile",
+        "client-a",
+        "--output",
+        "out.json",
+    ])
+    .output()
+    .await
+    .unwrap();
+    assert_success(&output);
+    assert!(
+        h.home
+            .path()
+            .join("cache/figma-cli/profiles/client-a")
+            .exists()
+    );
+    for (profile, entries) in [("client-a", 1), ("client-b", 0)] {
+        let output = run(&["cache", "list", "--json", "--profile", profile])
+            .output()
+            .await
+            .unwrap();
+        assert_success(&output);
+        assert_eq!(stdout_json(&output).as_array().unwrap().len(), entries);
+    }
+
+    let output = run(&["auth", "use", "missing"]).output().await.unwrap();
+    assert!(!output.status.success());
+}
+
+#[tokio::test]
+async fn test_selected_profile_takes_precedence_over_env_token() {
+    let h = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "7",
+            "email": "ana@example.com",
+            "handle": "Ana"
+        })))
+        .mount(&h.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/teams/22/projects"))
+        .and(header("X-Figma-Token", "figd_client_b"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "Client B",
+            "projects": []
+        })))
+        .mount(&h.server)
+        .await;
+
+    let output = h
+        .run(&[
+            "auth",
+            "login",
+            "figd_client_b",
+            "--profile",
+            "client-b",
+            "--team",
+            "22",
+        ])
+        .await;
+    assert_success(&output);
+
+    // FIGMA_TOKEN is set by the harness, but client-b's own login goes with its team and cache
+    let output = h.run(&["projects", "--profile", "client-b"]).await;
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["teamName"], "Client B");
+
+    let output = h
+        .command(&["projects"])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .env("FIGMA_PROFILE", "client-b")
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+    assert_eq!(stdout_json(&output)["teamName"], "Client B");
+
+    // A selected profile without a login does not borrow the env token
+    let output = h.run(&["projects", "--profile", "nobody"]).await;
+    assert!(!output.status.success());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_credentials_file_is_private_and_migrated() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let h = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me"))
+        .and(header("X-Figma-Token", "figd_stored_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "7",
+            "email": "ana@example.com",
+            "handle": "Ana"
+        })))
+        .mount(&h.server)
+        .await;
+
+    let config_dir = h.home.path().join("config/figma-cli");
+    let config_path = config_dir.join("config.toml");
+    let credentials_path = config_dir.join("credentials.toml");
+    std::fs::create_dir_all(&config_dir).unwrap();
+
+    // A token left in config.toml by an earlier version moves to the credentials file
+    std::fs::write(
+        &config_path,
+        "token = \"figd_stored_token\"\n\n[extraction]\ndepth = 3\n",
+    )
+    .unwrap();
+    let output = h
+        .command(&["auth", "test"])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .env_remove("FIGMA_TOKEN")
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    assert!(!config.contains("figd_stored_token"));
+    assert!(config.contains("depth = 3"));
+    let credentials = std::fs::read_to_string(&credentials_path).unwrap();
+    assert!(credentials.contains("figd_stored_token"));
+    let mode = std::fs::metadata(&credentials_path)
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    // A file other users can read is refused
+    std::fs::set_permissions(&credentials_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+    let output = h
+        .command(&["auth", "test"])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .env_remove("FIGMA_TOKEN")
+        .output()
+        .await
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("chmod 600"));
+
+    std::fs::set_permissions(&credentials_path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    let output = h
+        .command(&["auth", "test"])
+        .env("FIGMA_API_BASE", h.server.uri())
+        .env_remove("FIGMA_TOKEN")
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+
+    let output = h
+        .command(&["auth", "logout"])
+        .env_remove("FIGMA_TOKEN")
+        .output()
+        .await
+        .unwrap();
+    assert_success(&output);
+    assert!(!credentials_path.exists());
+}